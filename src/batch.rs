@@ -0,0 +1,195 @@
+// [[file:../dimer.note::95b2e0d7][95b2e0d7]]
+use super::*;
+
+use crate::rmsd::aligned_rmsd;
+use std::path::{Path, PathBuf};
+// 95b2e0d7 ends here
+
+// [[file:../dimer.note::4c8a1f36][4c8a1f36]]
+/// Options for running batch saddle point searches from one minimum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchOptions {
+    /// The number of dimer searches to run.
+    pub n_searches: usize,
+    /// Options for generating initial displacements of each search.
+    pub displacement: DisplacementOptions,
+    /// Saddle points with energy difference below this value can be
+    /// duplicates.
+    pub energy_tolerance: f64,
+    /// Saddle points with aligned RMSD below this value can be duplicates.
+    pub rmsd_tolerance: f64,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            n_searches: 10,
+            displacement: DisplacementOptions::default(),
+            energy_tolerance: 0.01,
+            rmsd_tolerance: 0.1,
+        }
+    }
+}
+
+/// The status of one search in batch saddle point searches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BatchStatus {
+    /// Converged to a new saddle point
+    Converged,
+    /// Failed to converge, or terminated with error
+    Failed,
+    /// Converged to a saddle point found before
+    Duplicate,
+}
+
+/// The summary of one search in batch saddle point searches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchSearch {
+    /// The status of the search
+    pub status: BatchStatus,
+    /// The index of the unique saddle point found, if converged
+    pub saddle: Option<usize>,
+    /// The number of translation iterations used
+    pub n_iterations: usize,
+    /// The number of force calls in each phase
+    pub force_calls: ForceCalls,
+    /// The reason of failure if any
+    pub message: Option<String>,
+}
+
+/// A unique saddle point found in batch saddle point searches
+#[derive(Debug, Clone)]
+pub struct UniqueSaddle {
+    /// The results at the saddle point when first found
+    pub saddle: DimerOutput,
+    /// The number of searches converged to this saddle point
+    pub count: usize,
+}
+
+/// Results of batch saddle point searches
+#[derive(Debug, Clone)]
+pub struct BatchOutput {
+    /// Unique saddle points in the order first found
+    pub saddles: Vec<UniqueSaddle>,
+    /// The summary of each search in order
+    pub searches: Vec<BatchSearch>,
+}
+// 4c8a1f36 ends here
+
+// [[file:../dimer.note::e7b35c18][e7b35c18]]
+/// Return the index of saddle point in `saddles` duplicated with `saddle`.
+fn find_duplicate(
+    saddles: &[UniqueSaddle],
+    saddle: &DimerOutput,
+    options: &BatchOptions,
+    elements: Option<&[String]>,
+) -> Result<Option<usize>> {
+    for (i, s) in saddles.iter().enumerate() {
+        if (s.saddle.total_energy - saddle.total_energy).abs() < options.energy_tolerance
+            && aligned_rmsd(&s.saddle.position, &saddle.position, elements)? < options.rmsd_tolerance
+        {
+            return Ok(Some(i));
+        }
+    }
+    Ok(None)
+}
+
+/// Return `path` with search index `i` inserted before its extension, such as
+/// `checkpoint.json` => `checkpoint.3.json`.
+fn indexed_path(path: &Path, i: usize) -> PathBuf {
+    match path.extension() {
+        Some(ext) => path.with_extension(format!("{i}.{}", ext.to_string_lossy())),
+        None => path.with_extension(i.to_string()),
+    }
+}
+
+/// Run dimer saddle point searches from atom `positions` at a minimum, each
+/// starting from a random initial displacement. `make_pot` creates the
+/// potential for each search. Converged saddle points are deduplicated by
+/// energy and aligned RMSD. If `elements` of atoms are provided, the RMSD
+/// will be invariant to permutations of atoms with the same element. The
+/// `checkpoint_file` and `trajectory_file` in `vars`, if any, are suffixed with
+/// the index of each search, so that searches will not overwrite each other.
+pub fn batch_search<P: EvaluateEnergyForce>(
+    positions: &[f64],
+    elements: Option<&[String]>,
+    vars: &UserOptions,
+    options: &BatchOptions,
+    mut make_pot: impl FnMut() -> P,
+) -> Result<BatchOutput> {
+    vars.validate()?;
+    if let Some(elements) = elements {
+        if 3 * elements.len() != positions.len() {
+            return Err(DimerError::InvalidInput(format!(
+                "expect {} elements for {} coordinates, but found {}",
+                positions.len() / 3,
+                positions.len(),
+                elements.len()
+            )));
+        }
+    }
+
+    let mut generator = DisplacementGenerator::new(options.displacement.clone());
+    let mut saddles: Vec<UniqueSaddle> = vec![];
+    let mut searches = vec![];
+    for i in 0..options.n_searches {
+        info!("batch saddle point search {}/{}", i + 1, options.n_searches);
+        let (center, orientation) = generator.generate(positions)?;
+        let mut dimer = Dimer::new(&center, &orientation, make_pot())?;
+        dimer.vars = vars.clone();
+        dimer.vars.checkpoint_file = vars.checkpoint_file.as_deref().map(|p| indexed_path(p, i));
+        dimer.vars.trajectory_file = vars.trajectory_file.as_deref().map(|p| indexed_path(p, i));
+        let search = match dimer.search() {
+            Ok(o) if o.converged() => match find_duplicate(&saddles, &o.saddle, options, elements)? {
+                Some(k) => {
+                    info!("search {} found duplicate saddle point {k}", i + 1);
+                    saddles[k].count += 1;
+                    BatchSearch {
+                        status: BatchStatus::Duplicate,
+                        saddle: Some(k),
+                        n_iterations: o.n_iterations,
+                        force_calls: o.force_calls,
+                        message: None,
+                    }
+                }
+                None => {
+                    info!("search {} found new saddle point {}", i + 1, saddles.len());
+                    saddles.push(UniqueSaddle {
+                        saddle: o.saddle,
+                        count: 1,
+                    });
+                    BatchSearch {
+                        status: BatchStatus::Converged,
+                        saddle: Some(saddles.len() - 1),
+                        n_iterations: o.n_iterations,
+                        force_calls: o.force_calls,
+                        message: None,
+                    }
+                }
+            },
+            Ok(o) => BatchSearch {
+                status: BatchStatus::Failed,
+                saddle: None,
+                n_iterations: o.n_iterations,
+                force_calls: o.force_calls,
+                message: Some(format!("{:?}", o.status)),
+            },
+            Err(e) => {
+                warn!("search {} failed: {e}", i + 1);
+                BatchSearch {
+                    status: BatchStatus::Failed,
+                    saddle: None,
+                    n_iterations: 0,
+                    force_calls: dimer.force_calls(),
+                    message: Some(e.to_string()),
+                }
+            }
+        };
+        searches.push(search);
+    }
+    info!("{} unique saddle points found in {} searches", saddles.len(), options.n_searches);
+
+    Ok(BatchOutput { saddles, searches })
+}
+// e7b35c18 ends here
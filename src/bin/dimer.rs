@@ -0,0 +1,127 @@
+// [[file:../../dimer.note::a2f95c60][a2f95c60]]
+use dimer::{Dimer, UserOptions};
+
+use gosh::gchemol;
+use gosh::model::BlackBoxModel;
+use gosh::prelude::ChemicalModel;
+use gchemol::prelude::*;
+use gchemol::Molecule;
+use gut::cli::*;
+use gut::prelude::*;
+use vecfx::*;
+
+use std::path::PathBuf;
+// a2f95c60 ends here
+
+// [[file:../../dimer.note::3b7d10e9][3b7d10e9]]
+/// Search for saddle point using DIMER algorithm. The initial dimer mode is
+/// read from velocities in the structure file.
+#[derive(Parser, Debug)]
+struct Cli {
+    #[clap(flatten)]
+    verbose: Verbosity,
+
+    /// The structure file of starting geometry, with initial dimer mode stored
+    /// as atom velocities.
+    input: PathBuf,
+
+    /// The directory of BlackBoxModel template for computing energy and forces.
+    #[clap(short = 'b', long)]
+    bbm_dir: PathBuf,
+
+    /// The config file for DIMER options in JSON or TOML format. Options can
+    /// also be set by `DIMER_*` environment variables, which take precedence.
+    #[clap(short = 'c', long)]
+    config: Option<PathBuf>,
+
+    /// The output file of saddle point geometry.
+    #[clap(short = 'o', long, default_value = "saddle.xyz")]
+    output: PathBuf,
+
+    /// The output file of the curvature mode at saddle point.
+    #[clap(short = 'm', long, default_value = "saddle-mode.txt")]
+    mode: PathBuf,
+
+    /// The output file of search summary in JSON format.
+    #[clap(short = 's', long, default_value = "dimer-summary.json")]
+    summary: PathBuf,
+}
+
+/// Summary of a DIMER saddle point search
+#[derive(Debug, Serialize)]
+struct Summary {
+    status: dimer::SearchStatus,
+    n_iterations: usize,
+    energy: f64,
+    curvature: f64,
+    curvatures: Vec<f64>,
+    fmax: f64,
+    force_calls: dimer::ForceCalls,
+}
+// 3b7d10e9 ends here
+
+// [[file:../../dimer.note::e0c84b17][e0c84b17]]
+/// Format curvature mode in xyz-like lines of element symbol and displacements.
+fn format_mode(mol: &Molecule, mode: &[f64]) -> String {
+    mol.symbols()
+        .zip(mode.as_3d())
+        .map(|(sym, [x, y, z])| format!("{sym:4}{x:-18.8}{y:-18.8}{z:-18.8}\n"))
+        .collect()
+}
+
+fn main() -> Result<()> {
+    let args = Cli::parse();
+    args.verbose.setup_logger();
+
+    let mut mol = Molecule::from_file(&args.input)?;
+    let center = mol.positions().collect_vec();
+    let orientation = mol.velocities().collect_vec();
+    ensure!(
+        orientation.as_flat().iter().any(|x| x.abs() > 0.0),
+        "no initial dimer mode found in velocities of {:?}",
+        args.input
+    );
+
+    let vars = UserOptions::load(args.config.as_deref())?;
+
+    let mut bbm = BlackBoxModel::from_dir(&args.bbm_dir)?;
+    let mut mol_pot = mol.clone();
+    let pot = |position: &[f64], force: &mut [f64]| {
+        mol_pot.set_positions(position.as_3d().to_vec());
+        let mp = bbm.compute(&mol_pot)?;
+        let energy = mp.get_energy().ok_or_else(|| format_err!("no energy computed"))?;
+        let forces = mp.get_forces().ok_or_else(|| format_err!("no forces computed"))?;
+        force.clone_from_slice(forces.as_flat());
+        Ok(energy)
+    };
+
+    let mut dimer = Dimer::new(center.as_flat(), orientation.as_flat(), pot)?;
+    dimer.vars = vars;
+    let o = dimer.search()?;
+    if !o.converged() {
+        warn!("saddle point search terminated without convergence: {:?}", o.status);
+    }
+
+    let saddle = &o.saddle;
+    mol.set_positions(saddle.position.as_3d().to_vec());
+    mol.to_file(&args.output)?;
+    println!("saddle point geometry written to {:?}", args.output);
+
+    gut::fs::write_to_file(&args.mode, &format_mode(&mol, &saddle.curvature_mode))?;
+    println!("curvature mode written to {:?}", args.mode);
+
+    let summary = Summary {
+        status: o.status,
+        n_iterations: o.n_iterations,
+        energy: saddle.total_energy,
+        curvature: saddle.curvature,
+        curvatures: saddle.modes.iter().map(|m| m.curvature).collect(),
+        fmax: saddle.effective_force.to_vector().amax(),
+        force_calls: o.force_calls,
+    };
+    gut::fs::write_to_file(&args.summary, &serde_json::to_string_pretty(&summary)?)?;
+    println!("search summary written to {:?}", args.summary);
+
+    Ok(())
+}
+// e0c84b17 ends here
@@ -11,7 +11,7 @@ use vecfx::*;
 
 // [[file:../dimer.note::2f6d095d][2f6d095d]]
 /// The beta value to determine the step of the steepest descent direction
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BetaKind {
     /// Polak-Ribiere
     PR,
@@ -30,9 +30,10 @@ impl Default for BetaKind {
 }
 
 /// Method for determining when to restart a CG optimization
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RestartMethod {
-    /// When the scalar-projection of the two previous gradients is above 0.2
+    /// When the current and previous gradients lose orthogonality:
+    /// `|g_k·g_{k-1}| >= 0.2 |g_k|^2`
     Powell,
     /// When `beta < 0`, CG restarts the conjugate gradient
     Negative,
@@ -45,7 +46,7 @@ impl Default for RestartMethod {
 }
 
 /// History data during conjugate gradient optimization
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ConjugateGradientState {
     /// The forces
     forces: DVector,
@@ -53,7 +54,7 @@ struct ConjugateGradientState {
     conjct: DVector,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConjugateGradient {
     /// The state of previous step
     state: Option<ConjugateGradientState>,
@@ -79,18 +80,34 @@ impl Default for ConjugateGradient {
     }
 }
 
+impl ConjugateGradient {
+    /// Construct CG with `beta` scheme and `restart` method.
+    pub fn new(beta: BetaKind, restart: RestartMethod) -> Self {
+        ConjugateGradient {
+            beta,
+            restart,
+            ..Default::default()
+        }
+    }
+
+    /// Forget the previous step, and restart from steepest descent direction.
+    pub fn reset(&mut self) {
+        self.state = None;
+    }
+}
+
 pub type CG = ConjugateGradient;
 // 2f6d095d ends here
 
 // [[file:../dimer.note::1e041d59][1e041d59]]
 impl ConjugateGradient {
     /// Return the new conjugate direction
-    pub fn propagate(&mut self, forces: &DVector) -> DVector {
+    pub fn propagate(&mut self, forces: &DVector) -> Result<DVector> {
         self.propagate_dimer(forces, None)
     }
 
     /// Return the new conjugate direction
-    pub fn propagate_dimer(&mut self, forces: &DVector, dimer_orientation: Option<&DVector>) -> DVector {
+    pub fn propagate_dimer(&mut self, forces: &DVector, dimer_orientation: Option<&DVector>) -> Result<DVector> {
         // FIXME: ad hoc hacking for dimer rotation
         let forces = dimer_orientation.map_or(forces.clone(), |tau| forces.vector_rejection(tau));
 
@@ -101,7 +118,13 @@ impl ConjugateGradient {
         });
 
         // udpate beta
-        let beta = self.beta.update(&forces, &state);
+        let beta = self.beta.update(&forces, state);
+        let beta = if beta.is_finite() {
+            beta
+        } else {
+            debug!("invalid beta in {:?} scheme: {beta}, restart CG", self.beta);
+            0.0
+        };
 
         // restart
         let beta = self.beta_damping
@@ -112,13 +135,12 @@ impl ConjugateGradient {
                 RestartMethod::Powell => {
                     let n = forces.norm_squared();
                     let m = forces.dot(&state.forces);
-                    if n / m >= 0.2 {
+                    if m.abs() >= 0.2 * n {
                         0.0
                     } else {
                         beta
                     }
                 }
-                _ => unimplemented!(),
             };
 
         // Now calculate the new steepest descent direction
@@ -131,7 +153,10 @@ impl ConjugateGradient {
         state.forces = forces.clone();
         state.conjct = disp.clone();
 
-        disp
+        if disp.iter().any(|x| !x.is_finite()) {
+            return Err(DimerError::Numerical(format!("invalid CG direction: {disp:?}")));
+        }
+        Ok(disp)
     }
 }
 
@@ -156,10 +181,6 @@ impl BetaKind {
                 let d = forces_this - forces_prev;
                 forces_this.norm_squared() / conjct_prev.dot(&d)
             }
-            _ => {
-                error!("unkown beta parameter scheme!");
-                unimplemented!()
-            }
         }
     }
 }
@@ -0,0 +1,98 @@
+// [[file:../dimer.note::41c7a9e3][41c7a9e3]]
+use super::*;
+
+use crate::search::SearchState;
+use std::path::{Path, PathBuf};
+// 41c7a9e3 ends here
+
+// [[file:../dimer.note::f6e2b078][f6e2b078]]
+/// Checkpoint data of a DIMER saddle point search for restart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// position vector of dimer center
+    center: DVector,
+    /// dimer orientation unit vector
+    orientation: DVector,
+    /// Dimer algorithm parameters
+    vars: UserOptions,
+    /// The state of saddle point search
+    state: SearchState,
+    /// The number of force calls so far
+    #[serde(default)]
+    force_calls: ForceCalls,
+    /// The mask of coordinates: 1 for free and 0 for frozen
+    #[serde(default)]
+    mask: Option<DVector>,
+    /// Higher curvature modes found in index-k saddle point search
+    #[serde(default)]
+    higher_modes: Vec<(f64, DVector)>,
+    /// Square roots of masses for each coordinate in mass-weighted mode
+    #[serde(default)]
+    sqrt_masses: Option<DVector>,
+}
+
+impl Checkpoint {
+    /// Save checkpoint into `path` in JSON format. The checkpoint is written
+    /// into a temporary file first and then renamed, so the previous one
+    /// remains intact if interrupted.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let s = serde_json::to_string_pretty(self)?;
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        gut::fs::write_to_file(&tmp, &s)?;
+        std::fs::rename(&tmp, path)?;
+        info!("checkpoint saved to {path:?}");
+        Ok(())
+    }
+
+    /// Load checkpoint from JSON file in `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let s = gut::fs::read_file(path)?;
+        let checkpoint = serde_json::from_str(&s)?;
+        Ok(checkpoint)
+    }
+}
+// f6e2b078 ends here
+
+// [[file:../dimer.note::0b93d5a4][0b93d5a4]]
+impl<'a> Dimer<'a> {
+    /// Return checkpoint data with search `state`.
+    pub(crate) fn checkpoint(&self, state: &SearchState) -> Checkpoint {
+        Checkpoint {
+            center: self.center.clone(),
+            orientation: self.orientation.clone(),
+            vars: self.vars.clone(),
+            state: state.clone(),
+            force_calls: self.ncalls,
+            mask: self.mask.clone(),
+            higher_modes: self.higher_modes.clone(),
+            sqrt_masses: self.sqrt_masses.clone(),
+        }
+    }
+
+    /// Restore a dimer from checkpoint file in `path`. Call `search` to
+    /// continue the saddle point search from where it stopped.
+    pub fn resume_from(path: impl AsRef<Path>, pot: impl EvaluateEnergyForce + 'a) -> Result<Self> {
+        let path = path.as_ref();
+        let checkpoint = Checkpoint::load(path)?;
+        let state = &checkpoint.state;
+        info!("resume dimer search from {path:?} after {} translation steps", state.n_iterations);
+        if let (Some(energy), Some(forces)) = (state.energy, &state.forces) {
+            info!("last energy = {energy:.5}; fmax = {:.4}", forces.amax());
+        }
+        let mut dimer = Self::new(checkpoint.center.as_slice(), checkpoint.orientation.as_slice(), pot)?;
+        dimer.vars = checkpoint.vars;
+        dimer.state = Some(checkpoint.state);
+        dimer.ncalls = checkpoint.force_calls;
+        // frozen coordinates must stay fixed after restart
+        dimer.mask = checkpoint.mask;
+        // initial guesses of deflated modes in the next iteration
+        dimer.higher_modes = checkpoint.higher_modes;
+        // center and orientation are saved in mass-weighted coordinates if any
+        dimer.sqrt_masses = checkpoint.sqrt_masses;
+        dimer.sync_dynamics();
+        Ok(dimer)
+    }
+}
+// 0b93d5a4 ends here
@@ -0,0 +1,150 @@
+// [[file:../dimer.note::3d6f9c20][3d6f9c20]]
+use super::*;
+
+use crate::fire::Fire;
+use crate::rmsd::aligned_rmsd;
+use crate::translation::limit_step_length;
+// 3d6f9c20 ends here
+
+// [[file:../dimer.note::a8e21b5f][a8e21b5f]]
+/// Options for connecting a saddle point to its adjacent minima
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConnectOptions {
+    /// The length of initial displacement from saddle point along curvature
+    /// mode on both sides.
+    pub displacement: f64,
+    /// Force component criteria for minimization.
+    pub fmax: f64,
+    /// Maximum number of FIRE steps in each minimization.
+    pub max_steps: usize,
+    /// Two minima within this energy difference can be the same.
+    pub energy_tolerance: f64,
+    /// Two minima within this RMSD after alignment can be the same.
+    pub rmsd_tolerance: f64,
+}
+
+impl Default for ConnectOptions {
+    fn default() -> Self {
+        Self {
+            displacement: 0.1,
+            fmax: 0.01,
+            max_steps: 1000,
+            energy_tolerance: 0.01,
+            rmsd_tolerance: 0.1,
+        }
+    }
+}
+
+/// A local minimum relaxed from saddle point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Minimum {
+    /// The position of the minimum
+    pub position: Vec<f64>,
+    /// The potential energy at the minimum
+    pub energy: f64,
+    /// True if the minimization converged within `fmax`
+    pub converged: bool,
+    /// The number of minimization steps used
+    pub n_steps: usize,
+}
+
+/// The reactant, saddle point and product connected by the minimum energy
+/// path
+#[derive(Debug, Clone)]
+pub struct Connection {
+    /// The minimum relaxed from the negative side of curvature mode
+    pub reactant: Minimum,
+    /// The saddle point
+    pub saddle: DimerOutput,
+    /// The minimum relaxed from the positive side of curvature mode
+    pub product: Minimum,
+    /// The energy barrier from reactant to product
+    pub forward_barrier: f64,
+    /// The energy barrier from product to reactant
+    pub reverse_barrier: f64,
+    /// True if both sides relax into the same minimum
+    pub same_minimum: bool,
+}
+// a8e21b5f ends here
+
+// [[file:../dimer.note::6b4d0e93][6b4d0e93]]
+/// Return the distance between `a` and `b`: RMSD after alignment for atom
+/// positions, or the plain Euclidean distance for other coordinates.
+fn structure_distance(a: &[f64], b: &[f64]) -> Result<f64> {
+    if a.len() % 3 == 0 {
+        aligned_rmsd(a, b, None)
+    } else {
+        Ok((a.to_vector() - b.to_vector()).norm())
+    }
+}
+
+impl<'a> Dimer<'a> {
+    /// Relax Cartesian `position` into a local minimum using FIRE, in the
+    /// same coordinates as dimer translation.
+    fn minimize_from(&mut self, position: DVector, options: &ConnectOptions) -> Result<Minimum> {
+        let vars = &self.vars;
+        let mut fire = Fire::new(vars.fire_dt, vars.fire_dt_max, vars.fire_alpha, vars.fire_n_min);
+        let max_step = vars.max_trans_step;
+        let mut x = self.to_internal(&position);
+        let mut n_steps = 0;
+        loop {
+            let f = self.compute_force_at(&x, ForcePhase::Minimization)?;
+            let energy = self.compute_energy()?;
+            let f = self.constrain(&f);
+            let converged = self.force_to_cartesian(&f).amax() < options.fmax;
+            if converged || n_steps >= options.max_steps {
+                if !converged {
+                    warn!("Minimization not converged within {n_steps} steps.");
+                }
+                return Ok(Minimum {
+                    position: self.to_cartesian(&x).as_slice().to_vec(),
+                    energy,
+                    converged,
+                    n_steps,
+                });
+            }
+            n_steps += 1;
+            x += limit_step_length(fire.propagate(&f), max_step);
+        }
+    }
+
+    /// Connect `saddle` to its two adjacent minima by displacing along
+    /// ±`curvature_mode` and relaxing on both sides. Return
+    /// `DimerError::ZeroOrientation` if `curvature_mode` has zero length.
+    pub fn connect(&mut self, saddle: &DimerOutput, options: &ConnectOptions) -> Result<Connection> {
+        let r0 = saddle.position.to_vector();
+        let mode = saddle.curvature_mode.to_vector();
+        if mode.norm() == 0.0 {
+            return Err(DimerError::ZeroOrientation);
+        }
+        let mode = mode.normalize();
+        let dx = options.displacement * mode;
+
+        info!("relax from negative side of curvature mode");
+        let reactant = self.minimize_from(&r0 - &dx, options)?;
+        info!("relax from positive side of curvature mode");
+        let product = self.minimize_from(&r0 + &dx, options)?;
+        // keep positions of `Dynamics` at dimer center
+        self.sync_dynamics();
+
+        let forward_barrier = saddle.total_energy - reactant.energy;
+        let reverse_barrier = saddle.total_energy - product.energy;
+        let same_minimum = (reactant.energy - product.energy).abs() < options.energy_tolerance
+            && structure_distance(&reactant.position, &product.position)? < options.rmsd_tolerance;
+        info!("forward barrier = {forward_barrier:.4}; reverse barrier = {reverse_barrier:.4}");
+        if same_minimum {
+            warn!("both sides of saddle point relax into the same minimum");
+        }
+
+        Ok(Connection {
+            reactant,
+            saddle: saddle.clone(),
+            product,
+            forward_barrier,
+            reverse_barrier,
+            same_minimum,
+        })
+    }
+}
+// 6b4d0e93 ends here
@@ -0,0 +1,86 @@
+// [[file:../dimer.note::c47e0a19][c47e0a19]]
+use super::*;
+
+use crate::rigid::{project_out, rigid_body_modes};
+// c47e0a19 ends here
+
+// [[file:../dimer.note::2d8b5f6a][2d8b5f6a]]
+impl<'a> Dimer<'a> {
+    /// Freeze degrees of freedom marked as true in `frozen`. Frozen
+    /// coordinates will never move, and they are excluded from the curvature
+    /// mode.
+    pub fn set_frozen_dofs(&mut self, frozen: &[bool]) -> Result<()> {
+        let n = self.center.len();
+        if frozen.len() != n {
+            return Err(DimerError::InvalidInput(format!(
+                "expect {n} flags for frozen coordinates, but found {}",
+                frozen.len()
+            )));
+        }
+        let mask = DVector::from_iterator(n, frozen.iter().map(|&x| if x { 0.0 } else { 1.0 }));
+        self.mask = Some(mask);
+        // remove frozen components from current dimer orientation
+        self.set_orientation(self.orientation.clone())
+    }
+
+    /// Freeze all coordinates of atoms in `atoms` (0-based indices).
+    pub fn set_frozen_atoms(&mut self, atoms: &[usize]) -> Result<()> {
+        let n = self.center.len();
+        let mut frozen = vec![false; n];
+        for &i in atoms {
+            if 3 * i + 3 > n {
+                return Err(DimerError::InvalidInput(format!("invalid atom index to freeze: {i}")));
+            }
+            frozen[3 * i..3 * i + 3].iter_mut().for_each(|x| *x = true);
+        }
+        self.set_frozen_dofs(&frozen)
+    }
+
+    /// Rebuild rigid-body modes from current dimer center if
+    /// `project_rigid_body` is enabled, and re-apply constraints on the dimer
+    /// orientation.
+    pub(crate) fn update_rigid_body_modes(&mut self) -> Result<()> {
+        if !self.vars.project_rigid_body {
+            self.rigid_modes.clear();
+            return Ok(());
+        }
+        let n = self.center.len();
+        if n % 3 != 0 {
+            return Err(DimerError::InvalidInput(format!(
+                "rigid-body projection requires Cartesian coordinates of atoms, but found {n} coordinates"
+            )));
+        }
+        let positions = self.to_cartesian(&self.center);
+        self.rigid_modes = rigid_body_modes(positions.as_slice(), self.sqrt_masses.as_ref());
+        debug!("project out {} rigid-body modes", self.rigid_modes.len());
+        self.set_orientation(self.orientation.clone())
+    }
+
+    /// Remove components of rigid-body motions and frozen coordinates from
+    /// vector `v`.
+    pub(crate) fn constrain(&self, v: &DVector) -> DVector {
+        let v = project_out(v, &self.rigid_modes);
+        match &self.mask {
+            Some(mask) => v.component_mul(mask),
+            None => v,
+        }
+    }
+
+    /// Remove constrained components and components along deflated modes
+    /// from vector `v` in the space of dimer orientation.
+    pub(crate) fn constrain_mode(&self, v: &DVector) -> DVector {
+        project_out(&self.constrain(v), &self.deflation)
+    }
+
+    /// Update dimer orientation with constraints applied.
+    pub(crate) fn set_orientation(&mut self, orientation: DVector) -> Result<()> {
+        let orientation = self.constrain_mode(&orientation);
+        let norm = orientation.norm();
+        if norm == 0.0 || !norm.is_finite() {
+            return Err(DimerError::ZeroOrientation);
+        }
+        self.orientation = orientation / norm;
+        Ok(())
+    }
+}
+// 2d8b5f6a ends here
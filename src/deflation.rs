@@ -0,0 +1,125 @@
+// [[file:../dimer.note::8e4d1a6b][8e4d1a6b]]
+use super::*;
+// 8e4d1a6b ends here
+
+// [[file:../dimer.note::f2a7c5d9][f2a7c5d9]]
+/// A curvature mode at dimer center
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurvatureMode {
+    /// The curvature along the mode
+    pub curvature: f64,
+    /// The normalized mode vector
+    pub mode: Vec<f64>,
+}
+
+impl<'a> Dimer<'a> {
+    /// Return an initial orientation for the next deflated mode, preferring
+    /// `guess` unless it lies in the space of deflated modes.
+    fn deflated_orientation(&self, guess: &DVector) -> DVector {
+        let v = self.constrain_mode(guess);
+        if v.norm() > 1E-3 * guess.norm() {
+            return v;
+        }
+        // fall back to the coordinate axis with largest remaining component
+        let n = guess.len();
+        (0..n)
+            .map(|i| self.constrain_mode(&DVector::from_fn(n, |j, _| if i == j { 1.0 } else { 0.0 })))
+            .max_by(|a, b| a.norm().total_cmp(&b.norm()))
+            .unwrap_or(v)
+    }
+
+    /// Find `count` curvature modes one by one, each in the subspace
+    /// orthogonal to current deflated modes and the modes found before. The
+    /// i-th mode starts from `guesses[i]` if available, or from `fallback`
+    /// otherwise. Found modes are appended into deflated modes. The forces and
+    /// energy at dimer center are evaluated only once, unless available in
+    /// `center`.
+    fn find_deflated_modes(
+        &mut self,
+        count: usize,
+        guesses: &[DVector],
+        fallback: &DVector,
+        mut center: Option<(DVector, f64)>,
+    ) -> Result<Vec<CurvatureMode>> {
+        let mut modes = vec![];
+        for i in 0..count {
+            info!("search for curvature mode {} in deflated subspace", self.deflation.len() + 1);
+            let guess = self.deflated_orientation(guesses.get(i).unwrap_or(fallback));
+            self.set_orientation(guess)?;
+            let rotation = self.next_minimum_mode_step(center.as_ref().map(|(f0, e0)| (f0, *e0)))?;
+            modes.push(CurvatureMode {
+                curvature: rotation.curvature_min,
+                mode: self.orientation.as_slice().to_vec(),
+            });
+            self.deflation.push(self.orientation.clone());
+            center = Some((rotation.raw_dimer.f0, rotation.energy));
+        }
+        Ok(modes)
+    }
+
+    /// Find `k` lowest curvature modes at dimer center in ascending order of
+    /// curvature. Each mode is found by dimer rotations (or Lanczos
+    /// iterations) in the subspace orthogonal to the modes found before. The
+    /// dimer orientation will be set to the lowest mode found. Returned modes
+    /// are in Cartesian coordinates.
+    pub fn lowest_modes(&mut self, k: usize) -> Result<Vec<CurvatureMode>> {
+        self.vars.validate()?;
+        let n = self.center.len();
+        if k == 0 || k > n {
+            return Err(DimerError::InvalidInput(format!("invalid number of modes {k} for {n} coordinates")));
+        }
+
+        let tau_ini = self.orientation.clone();
+        self.deflation.clear();
+        let found = self.find_deflated_modes(k, &[], &tau_ini, None);
+        self.deflation.clear();
+        let mut modes = found?;
+
+        modes.sort_by(|a, b| a.curvature.total_cmp(&b.curvature));
+        self.set_orientation(modes[0].mode.to_vector())?;
+        for m in modes.iter_mut() {
+            m.mode = self.mode_to_cartesian(&m.mode.to_vector()).as_slice().to_vec();
+        }
+        Ok(modes)
+    }
+}
+// f2a7c5d9 ends here
+
+// [[file:../dimer.note::1c5e9a07][1c5e9a07]]
+impl<'a> Dimer<'a> {
+    /// Find curvature modes to be inverted in translation for searching saddle
+    /// points of order `saddle_index`. The lowest mode along dimer orientation
+    /// with curvature `c_min` comes first, followed by higher modes found by
+    /// deflation, which start from the modes in previous iteration. Forces
+    /// `f0` and energy `e0` at dimer center are reused in deflation.
+    pub(crate) fn next_saddle_modes_step(&mut self, f0: &DVector, e0: f64, c_min: f64) -> Result<Vec<CurvatureMode>> {
+        let lowest = CurvatureMode {
+            curvature: c_min,
+            mode: self.orientation.as_slice().to_vec(),
+        };
+        let k = self.vars.saddle_index;
+        if k <= 1 {
+            self.higher_modes.clear();
+            return Ok(vec![lowest]);
+        }
+        let n = self.center.len();
+        if k > n {
+            return Err(DimerError::InvalidInput(format!("invalid saddle index {k} for {n} coordinates")));
+        }
+
+        let tau = self.orientation.clone();
+        let guesses = self.higher_modes.iter().map(|(_, m)| m.clone()).collect_vec();
+        self.deflation = vec![tau.clone()];
+        let found = self.find_deflated_modes(k - 1, &guesses, &tau, Some((f0.clone(), e0)));
+        self.deflation.clear();
+        // restore dimer orientation along the lowest mode
+        self.set_orientation(tau)?;
+        let higher = found?;
+        self.higher_modes = higher.iter().map(|m| (m.curvature, m.mode.to_vector())).collect();
+
+        let mut modes = vec![lowest];
+        modes.extend(higher);
+        Ok(modes)
+    }
+}
+// 1c5e9a07 ends here
@@ -1,35 +1,153 @@
 // [[file:../dimer.note::782181ce][782181ce]]
 use super::*;
+
+use crate::rotation::RotationOutput;
 // 782181ce ends here
 
 // [[file:../dimer.note::df98a463][df98a463]]
 /// Optimized results in DIMER algorithm
+#[derive(Debug, Clone)]
 pub struct DimerOutput {
+    /// The position of dimer center where the results evaluated.
+    pub position: Vec<f64>,
     /// DIMER energy, which is equal to potential energy when at dimer center.
     pub total_energy: f64,
     /// The effective force felt at dimer center (modified force for DIMER translation)
     pub effective_force: Vec<f64>,
     /// The optimized lowest curvature
     pub curvature: f64,
+    /// The accuracy estimate of curvature from the discrepancy between
+    /// forward and central differences.
+    pub curvature_error: Option<f64>,
     /// The optimized lowest curvature mode
     pub curvature_mode: Vec<f64>,
+    /// All curvature modes inverted in translation, with the lowest mode
+    /// along dimer orientation first. There are `saddle_index` modes.
+    pub modes: Vec<CurvatureMode>,
 }
 
 /// Main entry point for DIMER algorithm.
 impl<'a> Dimer<'a> {
     /// Carry out optimization in Dimer algorithm, and return the total energy and forces.
     pub fn evaluate(&mut self) -> Result<DimerOutput> {
-        let rotation = self.next_rotation_step(self.vars.max_num_rot)?;
-        let mut raw_dimer = rotation.raw_dimer;
+        let rotation = self.next_minimum_mode_step(None)?;
+        let raw_dimer = &rotation.raw_dimer;
         let c_min = rotation.curvature_min;
-        let effective_force = self.next_translation_step(&mut raw_dimer, c_min).as_slice().to_vec();
+        let modes = self.next_saddle_modes_step(&raw_dimer.f0, rotation.energy, c_min)?;
+        let effective_force = self.next_translation_step(raw_dimer, c_min);
+
+        Ok(self.output(&rotation, &effective_force, modes))
+    }
 
-        Ok(DimerOutput {
-            effective_force,
-            curvature: c_min,
+    /// Collect results at dimer center from `rotation` step, translation
+    /// `effective_force` and inverted curvature `modes`, all transformed into
+    /// Cartesian coordinates.
+    pub(crate) fn output(
+        &self,
+        rotation: &RotationOutput,
+        effective_force: &DVector,
+        modes: Vec<CurvatureMode>,
+    ) -> DimerOutput {
+        let modes = modes
+            .into_iter()
+            .map(|m| CurvatureMode {
+                curvature: m.curvature,
+                mode: self.mode_to_cartesian(&m.mode.to_vector()).as_slice().to_vec(),
+            })
+            .collect();
+        DimerOutput {
+            position: self.to_cartesian(&self.center).as_slice().to_vec(),
+            effective_force: self.force_to_cartesian(effective_force).as_slice().to_vec(),
+            curvature: rotation.curvature_min,
+            curvature_error: rotation.curvature_error,
             total_energy: rotation.energy,
-            curvature_mode: self.orientation.as_slice().to_vec(),
-        })
+            curvature_mode: self.mode_to_cartesian(&self.orientation).as_slice().to_vec(),
+            modes,
+        }
     }
 }
 // df98a463 ends here
+
+// [[file:../dimer.note::8c1e5b3d][8c1e5b3d]]
+/// The phase of DIMER algorithm in which forces are evaluated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ForcePhase {
+    Reinitialization,
+    Rotation,
+    Translation,
+    Hessian,
+    Minimization,
+}
+
+/// The number of force calls of the potential in each phase
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForceCalls {
+    /// Force calls for rebuilding dimer at a new center
+    pub reinitialization: usize,
+    /// Force calls in dimer rotation
+    pub rotation: usize,
+    /// Force calls in dimer translation
+    pub translation: usize,
+    /// Force calls for finite difference Hessian
+    #[serde(default)]
+    pub hessian: usize,
+    /// Force calls for relaxing into adjacent minima
+    #[serde(default)]
+    pub minimization: usize,
+}
+
+impl ForceCalls {
+    /// The total number of force calls in all phases.
+    pub fn total(&self) -> usize {
+        self.reinitialization + self.rotation + self.translation + self.hessian + self.minimization
+    }
+}
+
+impl<'a> Dimer<'a> {
+    /// Evaluate forces at `position` using the potential in `phase`, both in
+    /// coordinates used in dimer algorithm (mass-weighted if masses set). The
+    /// positions of `Dynamics` will be updated.
+    pub(crate) fn compute_force_at(&mut self, position: &DVector, phase: ForcePhase) -> Result<DVector> {
+        let x = self.to_cartesian(position);
+        let force = self.compute_cartesian_force_at(&x, phase)?;
+        Ok(self.force_to_internal(&force))
+    }
+
+    /// Evaluate Cartesian forces at Cartesian `position` using the potential
+    /// in `phase`. The positions of `Dynamics` will be updated.
+    pub(crate) fn compute_cartesian_force_at(&mut self, position: &DVector, phase: ForcePhase) -> Result<DVector> {
+        self.dynamics.set_position(position.as_slice());
+        let force = self.dynamics.get_force().map_err(DimerError::Potential)?.to_vector();
+        if force.iter().any(|x| !x.is_finite()) {
+            return Err(DimerError::NonFiniteForce);
+        }
+        match phase {
+            ForcePhase::Reinitialization => self.ncalls.reinitialization += 1,
+            ForcePhase::Rotation => self.ncalls.rotation += 1,
+            ForcePhase::Translation => self.ncalls.translation += 1,
+            ForcePhase::Hessian => self.ncalls.hessian += 1,
+            ForcePhase::Minimization => self.ncalls.minimization += 1,
+        }
+        Ok(force)
+    }
+
+    /// Return the potential energy at current positions of `Dynamics`.
+    pub(crate) fn compute_energy(&mut self) -> Result<f64> {
+        let energy = self.dynamics.get_energy().map_err(DimerError::Potential)?;
+        if !energy.is_finite() {
+            return Err(DimerError::NonFiniteEnergy(energy));
+        }
+        Ok(energy)
+    }
+
+    /// Return the number of force calls of the potential so far.
+    pub fn force_calls(&self) -> ForceCalls {
+        self.ncalls
+    }
+
+    /// Return true if the budget of force calls `max_force_calls` is used up.
+    pub(crate) fn budget_exhausted(&self) -> bool {
+        self.vars.max_force_calls.is_some_and(|n| self.ncalls.total() >= n)
+    }
+}
+// 8c1e5b3d ends here
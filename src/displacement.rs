@@ -0,0 +1,125 @@
+// [[file:../dimer.note::2e9b7d15][2e9b7d15]]
+use super::*;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+// 2e9b7d15 ends here
+
+// [[file:../dimer.note::c5f03a8e][c5f03a8e]]
+/// Parameters for generating random initial displacements from a minimum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplacementOptions {
+    /// Atoms (0-based) around which to displace. All atoms will be displaced
+    /// if empty.
+    pub atoms: Vec<usize>,
+    /// Atoms within this distance from any of `atoms` will be displaced.
+    pub radius: f64,
+    /// Standard deviation of the Gaussian displacement of each coordinate.
+    pub width: f64,
+    /// Use a random initial orientation instead of the normalized
+    /// displacement.
+    pub random_orientation: bool,
+    /// Seed of the random number generator for reproducible displacements.
+    pub seed: u64,
+}
+
+impl Default for DisplacementOptions {
+    fn default() -> Self {
+        Self {
+            atoms: vec![],
+            radius: 3.0,
+            width: 0.1,
+            random_orientation: false,
+            seed: 0,
+        }
+    }
+}
+
+/// Seeded generator of random initial dimer center and orientation
+#[derive(Debug, Clone)]
+pub struct DisplacementGenerator {
+    options: DisplacementOptions,
+    rng: StdRng,
+}
+
+/// Draw a random number from standard normal distribution using Box-Muller
+/// transform.
+fn gaussian(rng: &mut StdRng) -> f64 {
+    // u1 in (0, 1] to avoid ln(0)
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+impl DisplacementGenerator {
+    pub fn new(options: DisplacementOptions) -> Self {
+        let rng = StdRng::seed_from_u64(options.seed);
+        Self { options, rng }
+    }
+
+    /// Return indices of atoms in `coords` to be displaced.
+    fn selected_atoms(&self, coords: &[[f64; 3]]) -> Result<Vec<usize>> {
+        let natoms = coords.len();
+        if let Some(i) = self.options.atoms.iter().find(|&&i| i >= natoms) {
+            return Err(DimerError::InvalidInput(format!("invalid atom index {i} for {natoms} atoms")));
+        }
+        if self.options.atoms.is_empty() {
+            return Ok((0..natoms).collect());
+        }
+        let r2 = self.options.radius * self.options.radius;
+        let selected = (0..natoms)
+            .filter(|&i| {
+                self.options.atoms.iter().any(|&j| {
+                    let d2: f64 = (0..3).map(|k| (coords[i][k] - coords[j][k]).powi(2)).sum();
+                    d2 <= r2
+                })
+            })
+            .collect();
+        Ok(selected)
+    }
+
+    /// Generate a randomly displaced dimer center and its initial orientation
+    /// from atom `positions` at a minimum. Return the center and orientation
+    /// to be used in `Dimer::new`.
+    pub fn generate(&mut self, positions: &[f64]) -> Result<(Vec<f64>, Vec<f64>)> {
+        let n = positions.len();
+        if n == 0 || n % 3 != 0 {
+            return Err(DimerError::InvalidInput(format!(
+                "expect Cartesian coordinates of atoms, but found {n} coordinates"
+            )));
+        }
+        let width = self.options.width;
+        if !(width.is_finite() && width > 0.0) {
+            return Err(DimerError::InvalidOptions(format!("displacement width must be positive: {width}")));
+        }
+        let selected = self.selected_atoms(positions.as_3d())?;
+        let mut displacement = DVector::zeros(n);
+        for &i in &selected {
+            for k in 0..3 {
+                displacement[3 * i + k] = width * gaussian(&mut self.rng);
+            }
+        }
+        let orientation = if self.options.random_orientation {
+            let mut v = DVector::zeros(n);
+            for &i in &selected {
+                for k in 0..3 {
+                    v[3 * i + k] = gaussian(&mut self.rng);
+                }
+            }
+            v
+        } else {
+            displacement.clone()
+        };
+        let norm = orientation.norm();
+        if norm == 0.0 || !norm.is_finite() {
+            return Err(DimerError::InvalidInput("no atoms displaced for initial dimer".into()));
+        }
+        debug!("{} atoms displaced, displacement norm = {:.4}", selected.len(), displacement.norm());
+
+        let center = positions.to_vector() + displacement;
+        let orientation = orientation / norm;
+        Ok((center.as_slice().to_vec(), orientation.as_slice().to_vec()))
+    }
+}
+// c5f03a8e ends here
@@ -0,0 +1,81 @@
+// [[file:../dimer.note::b5d07a2e][b5d07a2e]]
+use super::*;
+// b5d07a2e ends here
+
+// [[file:../dimer.note::e7c31f98][e7c31f98]]
+/// Errors in DIMER algorithm
+#[derive(Debug)]
+pub enum DimerError {
+    /// The lengths of dimer center and orientation vectors are different
+    DimensionMismatch { center: usize, orientation: usize },
+    /// Invalid input data
+    InvalidInput(String),
+    /// The dimer orientation vector has zero length
+    ZeroOrientation,
+    /// The rotational force vanishes, so the rotation plane is undefined
+    ZeroRotationalForce,
+    /// Failure in evaluating energy and forces using the potential
+    Potential(gut::prelude::Error),
+    /// The potential returns non-finite energy
+    NonFiniteEnergy(f64),
+    /// The potential returns non-finite forces
+    NonFiniteForce,
+    /// Numerical failure in the optimization algorithms
+    Numerical(String),
+    /// Invalid user options
+    InvalidOptions(String),
+    /// Saddle point search terminated without convergence
+    NotConverged { status: SearchStatus, n_iterations: usize },
+    /// Other errors such as I/O failure
+    Other(gut::prelude::Error),
+}
+
+impl std::fmt::Display for DimerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::DimensionMismatch { center, orientation } => {
+                write!(f, "dimer center and orientation differ in length: {center} vs {orientation}")
+            }
+            Self::InvalidInput(msg) => write!(f, "invalid input: {msg}"),
+            Self::ZeroOrientation => write!(f, "dimer orientation vector has zero length"),
+            Self::ZeroRotationalForce => write!(f, "rotational force vanishes"),
+            Self::Potential(e) => write!(f, "potential evaluation failed: {e:?}"),
+            Self::NonFiniteEnergy(e) => write!(f, "potential returns non-finite energy: {e}"),
+            Self::NonFiniteForce => write!(f, "potential returns non-finite forces"),
+            Self::Numerical(msg) => write!(f, "numerical failure: {msg}"),
+            Self::InvalidOptions(msg) => write!(f, "invalid dimer options: {msg}"),
+            Self::NotConverged { status, n_iterations } => {
+                write!(f, "saddle point search not converged within {n_iterations} iterations: {status:?}")
+            }
+            Self::Other(e) => write!(f, "{e:?}"),
+        }
+    }
+}
+
+impl std::error::Error for DimerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Potential(e) | Self::Other(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<gut::prelude::Error> for DimerError {
+    fn from(e: gut::prelude::Error) -> Self {
+        Self::Other(e)
+    }
+}
+
+impl From<std::io::Error> for DimerError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Other(e.into())
+    }
+}
+
+impl From<serde_json::Error> for DimerError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Other(e.into())
+    }
+}
+// e7c31f98 ends here
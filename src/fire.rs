@@ -0,0 +1,101 @@
+// [[file:../dimer.note::7b21e6fd][7b21e6fd]]
+//! Implementation of the Fast Inertial Relaxation Engine (FIRE)
+//!
+//! # References
+//! - Bitzek, E. et al. Phys. Rev. Lett. 2006, 97, 170201.
+//! - <https://gitlab.com/ase/ase/-/blob/master/ase/optimize/fire.py>
+
+use super::*;
+// 7b21e6fd ends here
+
+// [[file:../dimer.note::4d0a93c8][4d0a93c8]]
+/// Factor for increasing time step
+const FIRE_F_INC: f64 = 1.1;
+/// Factor for decreasing time step
+const FIRE_F_DEC: f64 = 0.5;
+/// Factor for decreasing mixing parameter alpha
+const FIRE_F_ALPHA: f64 = 0.99;
+
+/// Fast Inertial Relaxation Engine driven by forces
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fire {
+    /// Initial time step
+    dt_start: f64,
+    /// Maximum time step
+    dt_max: f64,
+    /// Initial mixing parameter
+    alpha_start: f64,
+    /// Minimum number of steps in downhill direction before increasing time step
+    n_min: usize,
+
+    /// Current time step
+    dt: f64,
+    /// Current mixing parameter
+    alpha: f64,
+    /// Number of steps since last uphill motion
+    n_positive: usize,
+    /// Current velocities
+    velocity: Option<DVector>,
+}
+
+impl Fire {
+    pub fn new(dt: f64, dt_max: f64, alpha: f64, n_min: usize) -> Self {
+        Self {
+            dt_start: dt,
+            dt_max,
+            alpha_start: alpha,
+            n_min,
+            dt,
+            alpha,
+            n_positive: 0,
+            velocity: None,
+        }
+    }
+
+    /// Reset velocities, time step and mixing parameter to initial values.
+    pub fn reset(&mut self) {
+        self.dt = self.dt_start;
+        self.alpha = self.alpha_start;
+        self.n_positive = 0;
+        self.velocity = None;
+    }
+}
+// 4d0a93c8 ends here
+
+// [[file:../dimer.note::a85c3f16][a85c3f16]]
+impl Fire {
+    /// Update velocities using `forces` and return the displacement in MD step.
+    pub fn propagate(&mut self, forces: &DVector) -> DVector {
+        let mut v = match self.velocity.take() {
+            None => DVector::zeros(forces.len()),
+            Some(v) => {
+                let p = forces.dot(&v);
+                if p > 0.0 {
+                    // mixing velocities with forces
+                    let v = (1.0 - self.alpha) * &v + self.alpha * v.norm() * forces.normalize();
+                    if self.n_positive > self.n_min {
+                        self.dt = (self.dt * FIRE_F_INC).min(self.dt_max);
+                        self.alpha *= FIRE_F_ALPHA;
+                    }
+                    self.n_positive += 1;
+                    v
+                } else {
+                    // uphill motion: stop and decrease time step
+                    debug!("FIRE: uphill motion detected (P = {p:.4}), freeze velocities");
+                    self.dt *= FIRE_F_DEC;
+                    self.alpha = self.alpha_start;
+                    self.n_positive = 0;
+                    DVector::zeros(forces.len())
+                }
+            }
+        };
+
+        // Euler step with unit masses
+        v += self.dt * forces;
+        let displacement = self.dt * &v;
+        self.velocity = Some(v);
+
+        displacement
+    }
+}
+// a85c3f16 ends here
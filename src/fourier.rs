@@ -102,6 +102,9 @@ pub struct FourierState {
     pub r1_min: DVector,
     /// Extrapolated force of endpoint `1` when optimal rotation applied
     pub f1_min: DVector,
+    /// Extrapolated force of endpoint `2` when optimal rotation applied in
+    /// central difference
+    pub f2_min: Option<DVector>,
 }
 
 impl RotationState {
@@ -138,6 +141,41 @@ impl RawDimer {
         phi1: f64,
         theta: &DVector,
         extrapolated_force: bool,
+    ) -> FourierState {
+        self.fourier_rotate_with(r1_prime, f1_prime, None, phi1, theta, extrapolated_force)
+    }
+
+    /// Estimate optimal rotation using Fourier series with curvatures from
+    /// central differences.
+    ///
+    /// # Parameters
+    ///
+    /// * r1_prime: position of endpoint `1` in trial rotation
+    /// * f1_prime: force of endpoint `1` in trial rotation
+    /// * f2_prime: force of endpoint `2` in trial rotation
+    /// * phi1: trial rotation angle applied
+    /// * theta: rotation direction
+    /// * extrapolated_force: if f1 and f2 are extrapolated
+    pub fn fourier_rotate_central(
+        &mut self,
+        r1_prime: DVector,
+        f1_prime: DVector,
+        f2_prime: DVector,
+        phi1: f64,
+        theta: &DVector,
+        extrapolated_force: bool,
+    ) -> FourierState {
+        self.fourier_rotate_with(r1_prime, f1_prime, Some(f2_prime), phi1, theta, extrapolated_force)
+    }
+
+    fn fourier_rotate_with(
+        &mut self,
+        r1_prime: DVector,
+        f1_prime: DVector,
+        f2_prime: Option<DVector>,
+        phi1: f64,
+        theta: &DVector,
+        extrapolated_force: bool,
     ) -> FourierState {
         // get dimer state before trial rotation
         let n0 = self.dimer_axis();
@@ -145,10 +183,12 @@ impl RawDimer {
         let c0 = state.curvature();
         let c0d = state.curvature_derivative();
         let f1 = self.f1.clone();
+        let f2 = self.f2.clone();
 
-        // trial rotation: update dimer with new endpoint1
+        // trial rotation: update dimer with new endpoint1 (and endpoint2)
         self.r1 = r1_prime;
         self.f1 = f1_prime;
+        self.f2 = f2_prime;
         let c1 = self.extrapolate().curvature();
 
         let fourier_rot = FourierRotation::new(c0, c0d, phi1, c1);
@@ -178,10 +218,15 @@ impl RawDimer {
         // estimate force on new endpoint1
         let r1_min = self.get_endpoint1_after_rotation(&n0, &theta, phi_min);
         let f1_min = get_extrapolated_force(phi1, phi_min, &f1, &self.f1);
+        let f2_min = match (&f2, &self.f2) {
+            (Some(f2), Some(f2_prime)) => Some(get_extrapolated_force(phi1, phi_min, f2, f2_prime)),
+            _ => None,
+        };
 
         FourierState {
             r1_min,
             f1_min,
+            f2_min,
             phi_min,
             curvature_min,
         }
@@ -0,0 +1,140 @@
+// [[file:../dimer.note::4a7c0e58][4a7c0e58]]
+use super::*;
+
+type DMatrix = nalgebra::DMatrix<f64>;
+// 4a7c0e58 ends here
+
+// [[file:../dimer.note::b1e94f26][b1e94f26]]
+/// Conversion factor from sqrt(eV/Å²/amu) to wavenumber in cm⁻¹
+const WAVENUMBER_CONVERSION: f64 = 521.47090;
+
+/// Normal modes from diagonalization of the (mass-weighted) Hessian
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormalModes {
+    /// Eigenvalues of the Hessian in ascending order
+    pub eigenvalues: Vec<f64>,
+    /// Normalized eigenvectors transformed back to Cartesian coordinates, in
+    /// the same order as `eigenvalues`
+    pub modes: Vec<Vec<f64>>,
+    /// Harmonic frequencies in cm⁻¹, assuming energies in eV, lengths in Å
+    /// and masses in amu. Imaginary frequencies are given as negative
+    /// numbers.
+    pub frequencies: Vec<f64>,
+}
+
+/// Results of checking a stationary point as a first-order saddle point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaddleVerification {
+    /// The number of negative eigenvalues of the Hessian
+    pub n_negative: usize,
+    /// True if the Hessian has exactly one negative eigenvalue
+    pub is_first_order: bool,
+    /// The absolute overlap between the unstable mode of the Hessian and the
+    /// dimer curvature mode
+    pub mode_overlap: f64,
+    /// The normal modes at the stationary point
+    pub normal_modes: NormalModes,
+}
+
+/// Expand per-atom `masses` into a vector of square roots of masses for all
+/// `n` Cartesian coordinates.
+pub(crate) fn sqrt_masses(masses: &[f64], n: usize) -> Result<DVector> {
+    if 3 * masses.len() != n {
+        return Err(DimerError::InvalidInput(format!(
+            "expect {} atomic masses for {n} coordinates, but found {}",
+            n / 3,
+            masses.len()
+        )));
+    }
+    if let Some(m) = masses.iter().find(|&&m| !m.is_finite() || m <= 0.0) {
+        return Err(DimerError::InvalidInput(format!("invalid atomic mass: {m}")));
+    }
+    Ok(DVector::from_iterator(n, masses.iter().flat_map(|m| [m.sqrt(); 3])))
+}
+// b1e94f26 ends here
+
+// [[file:../dimer.note::7d2f8c91][7d2f8c91]]
+impl<'a> Dimer<'a> {
+    /// Build the Cartesian Hessian at Cartesian `position` by central
+    /// differences of forces with step size `hessian_step`. The Hessian is
+    /// symmetrized.
+    pub(crate) fn compute_hessian(&mut self, position: &DVector) -> Result<DMatrix> {
+        let n = position.len();
+        let h = self.vars.hessian_step;
+        let mut hessian = DMatrix::zeros(n, n);
+        for j in 0..n {
+            let mut r = position.clone();
+            r[j] += h;
+            let f1 = self.compute_cartesian_force_at(&r, ForcePhase::Hessian)?;
+            r[j] -= 2.0 * h;
+            let f2 = self.compute_cartesian_force_at(&r, ForcePhase::Hessian)?;
+            hessian.set_column(j, &((f2 - f1) / (2.0 * h)));
+        }
+        // keep positions of `Dynamics` at dimer center
+        self.sync_dynamics();
+        Ok((&hessian + hessian.transpose()) / 2.0)
+    }
+
+    /// Compute normal modes at `position` from finite difference Hessian,
+    /// which is mass-weighted using per-atom `masses` if provided.
+    pub fn normal_modes(&mut self, position: &[f64], masses: Option<&[f64]>) -> Result<NormalModes> {
+        let n = position.len();
+        let sqrt_m = match masses {
+            Some(masses) => sqrt_masses(masses, n)?,
+            None => DVector::from_element(n, 1.0),
+        };
+        let hessian = self.compute_hessian(&position.to_vector())?;
+        let mw_hessian = DMatrix::from_fn(n, n, |i, j| hessian[(i, j)] / (sqrt_m[i] * sqrt_m[j]));
+
+        let eigen = mw_hessian.symmetric_eigen();
+        let mut indices = (0..n).collect_vec();
+        indices.sort_by(|&i, &j| eigen.eigenvalues[i].total_cmp(&eigen.eigenvalues[j]));
+        let eigenvalues = indices.iter().map(|&i| eigen.eigenvalues[i]).collect_vec();
+        let modes = indices
+            .iter()
+            .map(|&i| {
+                // transform back to Cartesian coordinates
+                let mode = eigen.eigenvectors.column(i).component_div(&sqrt_m);
+                mode.normalize().as_slice().to_vec()
+            })
+            .collect();
+        let frequencies = eigenvalues
+            .iter()
+            .map(|&x| x.signum() * x.abs().sqrt() * WAVENUMBER_CONVERSION)
+            .collect();
+
+        Ok(NormalModes {
+            eigenvalues,
+            modes,
+            frequencies,
+        })
+    }
+
+    /// Check if `saddle` found in dimer search is a first-order saddle point
+    /// using finite difference Hessian, optionally mass-weighted using
+    /// per-atom `masses`. Eigenvalues below `-1E-4` times the largest
+    /// absolute eigenvalue are counted as negative.
+    pub fn verify_saddle(&mut self, saddle: &DimerOutput, masses: Option<&[f64]>) -> Result<SaddleVerification> {
+        let normal_modes = self.normal_modes(&saddle.position, masses)?;
+        let eigenvalues = &normal_modes.eigenvalues;
+        let scale = eigenvalues.iter().fold(0.0f64, |acc, x| acc.max(x.abs()));
+        let n_negative = eigenvalues.iter().filter(|&&x| x < -1E-4 * scale).count();
+        let mode_overlap = match normal_modes.modes.first() {
+            Some(mode) => mode.to_vector().dot(&saddle.curvature_mode.to_vector()).abs(),
+            None => 0.0,
+        };
+        info!("Hessian at saddle point has {n_negative} negative eigenvalues");
+        info!("overlap between unstable mode and dimer curvature mode: {mode_overlap:.4}");
+        if n_negative != 1 {
+            warn!("not a first-order saddle point: {n_negative} negative eigenvalues");
+        }
+
+        Ok(SaddleVerification {
+            n_negative,
+            is_first_order: n_negative == 1,
+            mode_overlap,
+            normal_modes,
+        })
+    }
+}
+// 7d2f8c91 ends here
@@ -0,0 +1,144 @@
+// [[file:../dimer.note::57c1e9ad][57c1e9ad]]
+//! Harmonic transition state theory rates with Vineyard prefactors
+//!
+//! # References
+//! - Vineyard, G. H. J. Phys. Chem. Solids 1957, 3, 121.
+
+use super::*;
+// 57c1e9ad ends here
+
+// [[file:../dimer.note::d93a4f6c][d93a4f6c]]
+/// Boltzmann constant in eV/K
+const BOLTZMANN: f64 = 8.617333262E-5;
+/// Speed of light in cm/s for converting wavenumbers to frequencies in Hz
+const SPEED_OF_LIGHT: f64 = 2.99792458E10;
+
+/// Options for harmonic transition state theory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HtstOptions {
+    /// Temperature in K.
+    pub temperature: f64,
+    /// Modes with absolute frequency below this value (in cm⁻¹) are treated
+    /// as rigid-body or frozen modes, and excluded from the prefactor.
+    pub zero_frequency: f64,
+}
+
+impl Default for HtstOptions {
+    fn default() -> Self {
+        Self {
+            temperature: 300.0,
+            zero_frequency: 20.0,
+        }
+    }
+}
+
+/// The rate constant in harmonic transition state theory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HtstRate {
+    /// The Vineyard prefactor in Hz
+    pub prefactor: f64,
+    /// The energy barrier in eV
+    pub barrier: f64,
+    /// Temperature in K
+    pub temperature: f64,
+    /// The rate constant in Hz
+    pub rate: f64,
+    /// The imaginary frequency of the unstable mode at saddle point in cm⁻¹,
+    /// given as a negative number
+    pub unstable_frequency: f64,
+    /// The number of stable modes at minimum used in the prefactor
+    pub n_modes_minimum: usize,
+    /// The number of stable modes at saddle point used in the prefactor
+    pub n_modes_saddle: usize,
+}
+
+/// Compute the Vineyard prefactor and rate constant from mass-weighted normal
+/// modes at `minimum` and `saddle`, with energy `barrier` in eV. The unstable
+/// mode at saddle point is identified by the largest overlap with dimer
+/// `curvature_mode`.
+pub fn vineyard_rate(
+    minimum: &NormalModes,
+    saddle: &NormalModes,
+    curvature_mode: &[f64],
+    barrier: f64,
+    options: &HtstOptions,
+) -> Result<HtstRate> {
+    let tau = curvature_mode.to_vector();
+    let (i_unstable, overlap) = saddle
+        .modes
+        .iter()
+        .map(|m| m.to_vector().dot(&tau).abs())
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .ok_or_else(|| DimerError::InvalidInput("no normal modes at saddle point".into()))?;
+    let unstable_frequency = saddle.frequencies[i_unstable];
+    info!("unstable mode at saddle point: {unstable_frequency:.2} cm⁻¹ (overlap = {overlap:.4})");
+    if unstable_frequency >= 0.0 {
+        return Err(DimerError::Numerical(format!(
+            "the mode along curvature mode is not unstable: {unstable_frequency:.2} cm⁻¹"
+        )));
+    }
+
+    // stable vibrational modes excluding rigid-body and unstable modes
+    let nu0 = options.zero_frequency;
+    let stable = |frequencies: &[f64], skip: Option<usize>| -> Result<Vec<f64>> {
+        let mut stable = vec![];
+        for (i, &f) in frequencies.iter().enumerate() {
+            if Some(i) == skip || f.abs() < nu0 {
+                continue;
+            }
+            if f < 0.0 {
+                return Err(DimerError::Numerical(format!("unexpected imaginary frequency: {f:.2} cm⁻¹")));
+            }
+            stable.push(f);
+        }
+        Ok(stable)
+    };
+    let nu_min = stable(&minimum.frequencies, None)?;
+    let nu_sad = stable(&saddle.frequencies, Some(i_unstable))?;
+    if nu_min.len() != nu_sad.len() + 1 {
+        return Err(DimerError::Numerical(format!(
+            "mismatched numbers of stable modes at minimum and saddle point: {} vs {}",
+            nu_min.len(),
+            nu_sad.len()
+        )));
+    }
+
+    // product of frequencies in log scale to avoid overflow
+    let ln_sum = |nu: &[f64]| nu.iter().map(|f| (f * SPEED_OF_LIGHT).ln()).sum::<f64>();
+    let prefactor = (ln_sum(&nu_min) - ln_sum(&nu_sad)).exp();
+    let temperature = options.temperature;
+    let rate = prefactor * (-barrier / (BOLTZMANN * temperature)).exp();
+    info!("Vineyard prefactor = {prefactor:.4e} Hz; rate at {temperature} K = {rate:.4e} Hz");
+
+    Ok(HtstRate {
+        prefactor,
+        barrier,
+        temperature,
+        rate,
+        unstable_frequency,
+        n_modes_minimum: nu_min.len(),
+        n_modes_saddle: nu_sad.len(),
+    })
+}
+
+impl<'a> Dimer<'a> {
+    /// Compute the rate constant in harmonic transition state theory for
+    /// escaping from `minimum` through `saddle` found in dimer search, with
+    /// finite difference Hessians mass-weighted using per-atom `masses` in
+    /// amu. Energies and lengths are assumed in eV and Å.
+    pub fn htst_rate(
+        &mut self,
+        minimum: &[f64],
+        saddle: &DimerOutput,
+        barrier: f64,
+        masses: &[f64],
+        options: &HtstOptions,
+    ) -> Result<HtstRate> {
+        let modes_min = self.normal_modes(minimum, Some(masses))?;
+        let modes_sad = self.normal_modes(&saddle.position, Some(masses))?;
+        vineyard_rate(&modes_min, &modes_sad, &saddle.curvature_mode, barrier, options)
+    }
+}
+// d93a4f6c ends here
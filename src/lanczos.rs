@@ -0,0 +1,182 @@
+// [[file:../dimer.note::0c6b3f7a][0c6b3f7a]]
+//! Lanczos method for finding the lowest curvature mode from finite difference
+//! Hessian-vector products, as an alternative to dimer rotations.
+//!
+//! # References
+//! - Malek, R.; Mousseau, N. Phys. Rev. E 2000, 62, 7723.
+//! - Olsen, R. A. et al. J. Chem. Phys. 2004, 121, 9776.
+
+use super::*;
+
+use crate::rotation::RotationOutput;
+
+type DMatrix = nalgebra::DMatrix<f64>;
+// 0c6b3f7a ends here
+
+// [[file:../dimer.note::9f31d6c4][9f31d6c4]]
+/// Return the lowest eigenvalue and its eigenvector of the symmetric
+/// tridiagonal matrix with diagonal `alphas` and off-diagonal `betas`.
+fn lowest_ritz_pair(alphas: &[f64], betas: &[f64]) -> (f64, DVector) {
+    let k = alphas.len();
+    let t = DMatrix::from_fn(k, k, |i, j| match i.abs_diff(j) {
+        0 => alphas[i],
+        1 => betas[i.min(j)],
+        _ => 0.0,
+    });
+    let eigen = t.symmetric_eigen();
+    let i = eigen.eigenvalues.imin();
+    (eigen.eigenvalues[i], eigen.eigenvectors.column(i).into_owned())
+}
+// 9f31d6c4 ends here
+
+// [[file:../dimer.note::5ad2e871][5ad2e871]]
+impl<'a> Dimer<'a> {
+    /// Return force differences between endpoint 1 and dimer center at
+    /// `r0` along unit vector `q`. The difference approximates `-dr H q`.
+    fn lanczos_force_difference(&mut self, r0: &DVector, f0: &DVector, q: &DVector) -> Result<DVector> {
+        let dr = self.vars.distance;
+        let [r1, r2] = compute_dimer_endpoints(r0, dr, q);
+        let f1 = self.compute_force_at(&r1, ForcePhase::Rotation)?;
+        if self.vars.use_central_difference {
+            let f2 = self.compute_force_at(&r2, ForcePhase::Rotation)?;
+            Ok((f1 - f2) / 2.0)
+        } else {
+            Ok(f1 - f0)
+        }
+    }
+
+    /// Find the lowest curvature mode at dimer center using Lanczos
+    /// iterations, starting from current dimer orientation. Each iteration
+    /// costs one force call (two in central difference mode). The forces and
+    /// energy at dimer center are reused from `center` if available.
+    pub(crate) fn next_lanczos_step(
+        &mut self,
+        n_max: usize,
+        center: Option<(&DVector, f64)>,
+    ) -> Result<RotationOutput> {
+        let tau_ini = self.orientation.clone();
+        let phi_tol = self.vars.min_rot_angle;
+        let dr = self.vars.distance;
+        let n = tau_ini.len();
+
+        let (raw_dimer, e0) = self.reinitialize(center)?;
+        let RawDimer { r0, f0, f1, f2, .. } = raw_dimer;
+        // force difference along current orientation is available already
+        let df = match &f2 {
+            Some(f2) => (&f1 - f2) / 2.0,
+            None => &f1 - &f0,
+        };
+        let mut basis = vec![self.orientation.clone()];
+        let mut dforces = vec![df];
+        let mut alphas = vec![];
+        let mut betas = vec![];
+        let mut mode = self.orientation.clone();
+        let mut coeffs;
+        let mut curvature_min;
+        let mut niter = 0;
+        loop {
+            niter += 1;
+            info!("dimer lanczos iteration {niter}");
+            let j = niter - 1;
+            // Hessian-vector product from finite differences of forces
+            let hq = self.constrain_mode(&(-&dforces[j] / dr));
+            let alpha = basis[j].dot(&hq);
+            alphas.push(alpha);
+
+            // Ritz approximation of the lowest mode in Krylov subspace
+            let (theta, y) = lowest_ritz_pair(&alphas, &betas);
+            let mut mode_new = basis.iter().zip(y.iter()).fold(DVector::zeros(n), |acc, (q, y)| acc + q * *y);
+            let norm = mode_new.norm();
+            mode_new /= norm;
+            coeffs = y / norm;
+            // keep the mode in the same direction as dimer orientation
+            if mode_new.dot(&tau_ini) < 0.0 {
+                mode_new = -mode_new;
+                coeffs = -coeffs;
+            }
+            let phi = mode_new.dot(&mode).abs().min(1.0).acos();
+            mode = mode_new;
+            curvature_min = theta;
+            info!("{:^15}{:^15}", "phi/deg", "c_min_est");
+            info!("{:^15.2}{:^-15.3}", phi.to_degrees(), curvature_min);
+            self.record(IterationRecord {
+                kind: IterationKind::Rotation,
+                iteration: niter,
+                energy: e0,
+                curvature_est: Some(curvature_min),
+                curvature: curvature_min,
+                phi_est: None,
+                phi_min: Some(phi),
+                fmax: None,
+                rotation_angle: Some(mode.cosine_similarity(&tau_ini).acos()),
+                step_length: None,
+                force_calls: self.ncalls.total(),
+            })?;
+
+            if niter > 1 && phi < phi_tol {
+                info!("Lowest mode converged within {niter} lanczos iterations.");
+                break;
+            }
+            if niter >= n_max {
+                warn!("Max allowed iterations {n_max} reached, but lanczos iterations not converged yet.");
+                break;
+            }
+            if self.budget_exhausted() {
+                warn!("Budget of force calls exhausted, but lanczos iterations not converged yet.");
+                break;
+            }
+            if basis.len() == n {
+                info!("Krylov subspace spans all {n} coordinates.");
+                break;
+            }
+
+            // next Lanczos vector with full reorthogonalization
+            let mut w = &hq - alpha * &basis[j];
+            if let Some(&beta) = betas.last() {
+                w -= beta * &basis[j - 1];
+            }
+            for q in basis.iter() {
+                w -= w.dot(q) * q;
+            }
+            let beta = w.norm();
+            if beta < 1E-8 * hq.norm() {
+                info!("Krylov subspace is invariant after {niter} lanczos iterations.");
+                break;
+            }
+            let q = w / beta;
+            let df = self.lanczos_force_difference(&r0, &f0, &q)?;
+            betas.push(beta);
+            basis.push(q);
+            dforces.push(df);
+        }
+
+        // forces on dimer endpoints along the lowest mode
+        let r1 = &r0 + dr * &mode;
+        let (f1, f2) = if self.vars.use_extrapolated_force {
+            let df = dforces.iter().zip(coeffs.iter()).fold(DVector::zeros(n), |acc, (df, c)| acc + df * *c);
+            let f2 = f2.map(|_| &f0 - &df);
+            (&f0 + df, f2)
+        } else {
+            let f1 = self.compute_force_at(&r1, ForcePhase::Rotation)?;
+            let f2 = match f2 {
+                Some(_) => Some(self.compute_force_at(&(&r0 - dr * &mode), ForcePhase::Rotation)?),
+                None => None,
+            };
+            (f1, f2)
+        };
+        self.set_orientation(mode)?;
+        let raw_dimer = RawDimer { r0, r1, f0, f1, f2 };
+
+        let phi = self.orientation.cosine_similarity(&tau_ini).acos().to_degrees();
+        info!("Total rotational angle = {phi:.2}°; optimized curvature = {curvature_min}");
+        let curvature_error = raw_dimer.curvature_error();
+        Ok(RotationOutput {
+            raw_dimer,
+            energy: e0,
+            curvature_min,
+            n_iterations: niter,
+            curvature_error,
+        })
+    }
+}
+// 5ad2e871 ends here
@@ -0,0 +1,99 @@
+// [[file:../dimer.note::0d6e9b51][0d6e9b51]]
+//! Implementation of the limited-memory BFGS (L-BFGS) algorithm
+//!
+//! # References
+//! - Nocedal, J. Math. Comp. 1980, 35, 773–782.
+//! - <https://en.wikipedia.org/wiki/Limited-memory_BFGS>
+
+use super::*;
+use std::collections::VecDeque;
+// 0d6e9b51 ends here
+
+// [[file:../dimer.note::8f2c4a17][8f2c4a17]]
+/// History data of one step during L-BFGS optimization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LbfgsPair {
+    /// The change of positions
+    s: DVector,
+    /// The change of gradients
+    y: DVector,
+    /// 1 / (y·s)
+    rho: f64,
+}
+
+/// Limited-memory BFGS optimizer driven by forces
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lbfgs {
+    /// The number of steps kept in history
+    memory: usize,
+
+    /// The scaling factor of the initial inverse Hessian
+    h0: f64,
+
+    /// The most recent pairs of position and gradient changes
+    history: VecDeque<LbfgsPair>,
+
+    /// The positions and forces of previous step
+    previous: Option<(DVector, DVector)>,
+}
+
+impl Lbfgs {
+    /// Construct L-BFGS with `memory` steps kept in history. `h0` is the
+    /// scaling factor of initial inverse Hessian used when history is empty.
+    pub fn new(memory: usize, h0: f64) -> Self {
+        Self {
+            memory: memory.max(1),
+            h0,
+            history: VecDeque::new(),
+            previous: None,
+        }
+    }
+
+    /// Clear all history data.
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.previous = None;
+    }
+}
+// 8f2c4a17 ends here
+
+// [[file:../dimer.note::c3b7e925][c3b7e925]]
+impl Lbfgs {
+    /// Update history with current `positions` and `forces`, and return the
+    /// quasi-Newton displacement.
+    pub fn propagate(&mut self, positions: &DVector, forces: &DVector) -> DVector {
+        if let Some((x_prev, f_prev)) = self.previous.take() {
+            let s = positions - x_prev;
+            // gradient is the negative of forces
+            let y = f_prev - forces;
+            let sy = s.dot(&y);
+            // skip update when curvature condition is not satisfied
+            if sy > 1e-10 {
+                self.history.push_back(LbfgsPair { s, y, rho: 1.0 / sy });
+                if self.history.len() > self.memory {
+                    self.history.pop_front();
+                }
+            } else {
+                debug!("skip L-BFGS update due to non-positive curvature: s·y = {sy}");
+            }
+        }
+        self.previous = Some((positions.clone(), forces.clone()));
+
+        // the two-loop recursion
+        let mut q = -forces;
+        let mut alpha = vec![0.0; self.history.len()];
+        for (i, p) in self.history.iter().enumerate().rev() {
+            alpha[i] = p.rho * p.s.dot(&q);
+            q -= alpha[i] * &p.y;
+        }
+        let gamma = self.history.back().map_or(self.h0, |p| p.s.dot(&p.y) / p.y.norm_squared());
+        let mut r = gamma * q;
+        for (i, p) in self.history.iter().enumerate() {
+            let beta = p.rho * p.y.dot(&r);
+            r += (alpha[i] - beta) * &p.s;
+        }
+
+        -r
+    }
+}
+// c3b7e925 ends here
@@ -1,10 +1,28 @@
 // [[file:../dimer.note::c6f8257d][c6f8257d]]
+mod batch;
 mod cg;
+mod checkpoint;
+mod connect;
+mod constraint;
+mod deflation;
 mod dimer;
+mod displacement;
+mod error;
+mod fire;
 mod fourier;
+mod hessian;
+mod htst;
+mod lanczos;
+mod lbfgs;
+mod massweight;
 mod options;
+pub mod potentials;
 mod raw;
+mod rigid;
+mod rmsd;
 mod rotation;
+mod search;
+mod trajectory;
 mod translation;
 
 #[cfg(test)]
@@ -20,6 +38,7 @@ use std::f64::consts::PI;
 use vecfx::*;
 
 type DVector = nalgebra::DVector<f64>;
+type Result<T, E = DimerError> = std::result::Result<T, E>;
 
 use raw::*;
 // 1e3853ed ends here
@@ -37,26 +56,76 @@ pub struct Dimer<'a> {
 
     /// Dimer algorithm parameters
     pub vars: UserOptions,
+
+    /// The state of saddle point search to be continued
+    state: Option<search::SearchState>,
+
+    /// The records of rotation and translation iterations
+    records: Vec<IterationRecord>,
+
+    /// The number of force calls of the potential
+    ncalls: ForceCalls,
+
+    /// The mask of coordinates: 1 for free and 0 for frozen
+    mask: Option<DVector>,
+
+    /// Orthonormal rigid-body modes to be projected out
+    rigid_modes: Vec<DVector>,
+
+    /// Orthonormal curvature modes excluded from dimer rotation
+    deflation: Vec<DVector>,
+
+    /// Curvatures and modes above the lowest one to be inverted in index-k
+    /// saddle point search
+    higher_modes: Vec<(f64, DVector)>,
+
+    /// Square roots of masses for each coordinate in mass-weighted mode
+    sqrt_masses: Option<DVector>,
 }
 
 impl<'a> Dimer<'a> {
     /// Construct a dimer from center and axis orientation.
-    pub fn new(center: &[f64], orientation: &[f64], pot: impl EvaluateEnergyForce + 'a) -> Self {
-        assert_eq!(center.len(), orientation.len(), "invalid data: {center:?}, {orientation:?}");
+    pub fn new(center: &[f64], orientation: &[f64], pot: impl EvaluateEnergyForce + 'a) -> Result<Self> {
+        if center.len() != orientation.len() {
+            return Err(DimerError::DimensionMismatch {
+                center: center.len(),
+                orientation: orientation.len(),
+            });
+        }
         let dynamics = Dynamics::new(center, pot);
-        let orientation = orientation.to_vector().normalize();
-        Self {
+        let mut dimer = Self {
             center: center.to_vector(),
             dynamics,
-            orientation,
+            orientation: DVector::zeros(center.len()),
             vars: UserOptions::default(),
-        }
+            state: None,
+            records: vec![],
+            ncalls: ForceCalls::default(),
+            mask: None,
+            rigid_modes: vec![],
+            deflation: vec![],
+            higher_modes: vec![],
+            sqrt_masses: None,
+        };
+        dimer.set_orientation(orientation.to_vector())?;
+        Ok(dimer)
     }
 }
 
+pub use crate::batch::{batch_search, BatchOptions, BatchOutput, BatchSearch, BatchStatus, UniqueSaddle};
+pub use crate::cg::{BetaKind, RestartMethod};
+pub use crate::checkpoint::Checkpoint;
+pub use crate::connect::{ConnectOptions, Connection, Minimum};
+pub use crate::deflation::CurvatureMode;
 pub use crate::dimer::*;
+pub use crate::displacement::{DisplacementGenerator, DisplacementOptions};
+pub use crate::error::DimerError;
+pub use crate::hessian::{NormalModes, SaddleVerification};
+pub use crate::htst::{vineyard_rate, HtstOptions, HtstRate};
+pub use crate::search::*;
+pub use crate::trajectory::*;
 pub use gosh::optim::EvaluateEnergyForce;
-pub use options::UserOptions;
+pub use options::{TranslationMethod, UserOptions};
 // a7df26ce ends here
 
 // [[file:../dimer.note::cfd3ba0e][cfd3ba0e]]
@@ -77,5 +146,22 @@ pub mod docs {
     export_doc!(rotation);
     export_doc!(translation);
     export_doc!(cg);
+    export_doc!(lbfgs);
+    export_doc!(fire);
+    export_doc!(search);
+    export_doc!(checkpoint);
+    export_doc!(trajectory);
+    export_doc!(error);
+    export_doc!(constraint);
+    export_doc!(rigid);
+    export_doc!(hessian);
+    export_doc!(lanczos);
+    export_doc!(deflation);
+    export_doc!(displacement);
+    export_doc!(rmsd);
+    export_doc!(batch);
+    export_doc!(connect);
+    export_doc!(htst);
+    export_doc!(massweight);
 }
 // cfd3ba0e ends here
@@ -0,0 +1,76 @@
+// [[file:../dimer.note::9c2b6e14][9c2b6e14]]
+use super::*;
+
+use crate::hessian::sqrt_masses;
+// 9c2b6e14 ends here
+
+// [[file:../dimer.note::e5a80d3b][e5a80d3b]]
+impl<'a> Dimer<'a> {
+    /// Run dimer rotation and translation in mass-weighted coordinates
+    /// q = sqrt(m) x using per-atom `masses`, so that the curvature mode is
+    /// the vibrational normal mode. Curvatures and step sizes are then in
+    /// mass-weighted units, but reported positions, effective forces and
+    /// modes remain in Cartesian coordinates.
+    pub fn set_masses(&mut self, masses: &[f64]) -> Result<()> {
+        let sqrt_m = sqrt_masses(masses, self.center.len())?;
+        let center = self.to_cartesian(&self.center);
+        let orientation = self.mode_to_cartesian(&self.orientation);
+        self.center = center.component_mul(&sqrt_m);
+        self.sqrt_masses = Some(sqrt_m);
+        self.sync_dynamics();
+        // modes in previous coordinates are no longer valid
+        self.rigid_modes.clear();
+        self.higher_modes.clear();
+        // a direction dx in Cartesian coordinates is sqrt(m) dx in
+        // mass-weighted coordinates
+        self.set_orientation(self.to_internal(&orientation))?;
+        self.update_rigid_body_modes()
+    }
+
+    /// Transform Cartesian positions or displacements `x` into coordinates
+    /// used in dimer algorithm.
+    pub(crate) fn to_internal(&self, x: &DVector) -> DVector {
+        match &self.sqrt_masses {
+            Some(sqrt_m) => x.component_mul(sqrt_m),
+            None => x.clone(),
+        }
+    }
+
+    /// Transform positions `q` used in dimer algorithm into Cartesian
+    /// coordinates.
+    pub(crate) fn to_cartesian(&self, q: &DVector) -> DVector {
+        match &self.sqrt_masses {
+            Some(sqrt_m) => q.component_div(sqrt_m),
+            None => q.clone(),
+        }
+    }
+
+    /// Transform forces `f` used in dimer algorithm into Cartesian forces.
+    pub(crate) fn force_to_cartesian(&self, f: &DVector) -> DVector {
+        match &self.sqrt_masses {
+            Some(sqrt_m) => f.component_mul(sqrt_m),
+            None => f.clone(),
+        }
+    }
+
+    /// Transform Cartesian forces `f` into forces used in dimer algorithm.
+    pub(crate) fn force_to_internal(&self, f: &DVector) -> DVector {
+        self.to_cartesian(f)
+    }
+
+    /// Transform a unit vector `tau` used in dimer algorithm into a unit
+    /// vector in Cartesian coordinates.
+    pub(crate) fn mode_to_cartesian(&self, tau: &DVector) -> DVector {
+        match &self.sqrt_masses {
+            Some(_) => self.to_cartesian(tau).normalize(),
+            None => tau.clone(),
+        }
+    }
+
+    /// Move the positions of `Dynamics` to dimer center.
+    pub(crate) fn sync_dynamics(&mut self) {
+        let x = self.to_cartesian(&self.center);
+        self.dynamics.set_position(x.as_slice());
+    }
+}
+// e5a80d3b ends here
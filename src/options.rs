@@ -1,6 +1,29 @@
 // [[file:../dimer.note::c38894e0][c38894e0]]
 use super::*;
 
+use crate::cg::{BetaKind, RestartMethod};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Algorithms for taking DIMER translation steps on the effective force
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TranslationMethod {
+    /// Steepest descent scaled by `trans_step_size`
+    SteepestDescent,
+    /// Limited-memory BFGS
+    LBFGS,
+    /// Fast Inertial Relaxation Engine, robust for noisy forces
+    FIRE,
+    /// Conjugate gradient with finite difference line search (Henkelman1999JCP)
+    CG,
+}
+
+impl Default for TranslationMethod {
+    fn default() -> Self {
+        TranslationMethod::SteepestDescent
+    }
+}
+
 /// Options for tuning DIMER algorithm from user side
 #[derive(Deserialize, Debug, Clone, Serialize)]
 #[serde(default)]
@@ -31,6 +54,106 @@ pub struct UserOptions {
     /// Use Conjugate gradient algorithm to determine the rotation plane,
     /// instead of simple steepest descent direction.
     pub use_cg_rot: bool,
+
+    /// Use L-BFGS algorithm to determine the rotation plane (Kastner2008JCP).
+    /// This takes precedence over `use_cg_rot`.
+    pub use_lbfgs_rot: bool,
+
+    /// Evaluate forces on both dimer endpoints, and estimate rotational
+    /// force and curvature by central differences.
+    pub use_central_difference: bool,
+
+    /// Find the lowest curvature mode by Lanczos iterations instead of
+    /// dimer rotations.
+    pub use_lanczos: bool,
+
+    /// Maximum number of Lanczos iterations in each rotation step.
+    pub lanczos_max_iterations: usize,
+
+    /// The order of saddle point to search for, i.e. the number of lowest
+    /// curvature modes along which the forces are inverted in translation.
+    pub saddle_index: usize,
+
+    /// Maximum number of translation steps allowed in saddle point search.
+    pub max_num_trans: usize,
+
+    /// Maximum number of force calls allowed in saddle point search. The
+    /// budget is checked between rotation (or Lanczos) iterations, so it can
+    /// be exceeded by at most `saddle_index * (1 + 2 * m)` force calls, where
+    /// `m` is 2 with `use_central_difference` or 1 otherwise.
+    pub max_force_calls: Option<usize>,
+
+    /// Scaling factor applied to the effective force in a steepest descent
+    /// translation step.
+    pub trans_step_size: f64,
+
+    /// The algorithm for taking translation steps.
+    pub translation_method: TranslationMethod,
+
+    /// The maximum length of a translation step.
+    pub max_trans_step: f64,
+
+    /// Fixed step length for dragging the dimer uphill when the curvature is
+    /// positive.
+    pub uphill_step_size: f64,
+
+    /// Take a Newton step along the dimer orientation using `|c_min|` when
+    /// the curvature is negative.
+    pub use_newton_step_along_mode: bool,
+
+    /// Project rigid-body translations and rotations out of the dimer
+    /// orientation, rotational force and effective force. Suitable for
+    /// gas-phase molecules in Cartesian coordinates only.
+    pub project_rigid_body: bool,
+
+    /// Adapt the maximum translation step from the ratio of actual to
+    /// predicted energy change.
+    pub use_trust_radius: bool,
+
+    /// Initial trust radius for translation steps.
+    pub trust_radius: f64,
+
+    /// Lower bound of the trust radius.
+    pub min_trust_radius: f64,
+
+    /// The number of previous steps kept in L-BFGS history for translation
+    /// and rotation.
+    pub lbfgs_memory: usize,
+
+    /// Initial time step in FIRE.
+    pub fire_dt: f64,
+
+    /// Maximum time step in FIRE.
+    pub fire_dt_max: f64,
+
+    /// Initial mixing parameter in FIRE.
+    pub fire_alpha: f64,
+
+    /// Minimum number of downhill steps before increasing time step in FIRE.
+    pub fire_n_min: usize,
+
+    /// The beta scheme of conjugate gradient in CG translation.
+    pub cg_trans_beta: BetaKind,
+
+    /// The restart method of conjugate gradient in CG translation.
+    pub cg_trans_restart: RestartMethod,
+
+    /// Trial step length for finite difference estimate of the curvature
+    /// along CG direction in translation.
+    pub cg_trans_trial_step: f64,
+
+    /// Step size for finite difference Hessian in saddle point verification.
+    pub hessian_step: f64,
+
+    /// Write checkpoint of the search state into this JSON file for restart.
+    pub checkpoint_file: Option<PathBuf>,
+
+    /// Write checkpoint every N translation steps.
+    pub checkpoint_interval: usize,
+
+    /// Stream records of rotation and translation iterations into this file
+    /// in JSON Lines format.
+    pub trajectory_file: Option<PathBuf>,
 }
 
 impl Default for UserOptions {
@@ -44,7 +167,197 @@ impl Default for UserOptions {
             max_num_rot: 5,
             use_extrapolated_force: false,
             use_cg_rot: true,
+            use_lbfgs_rot: false,
+            use_central_difference: false,
+            use_lanczos: false,
+            lanczos_max_iterations: 20,
+            saddle_index: 1,
+            max_num_trans: 100,
+            max_force_calls: None,
+            trans_step_size: 0.01,
+            translation_method: TranslationMethod::default(),
+            max_trans_step: 0.1,
+            uphill_step_size: 0.1,
+            use_newton_step_along_mode: false,
+            project_rigid_body: false,
+            use_trust_radius: false,
+            trust_radius: 0.1,
+            min_trust_radius: 1E-3,
+            lbfgs_memory: 5,
+            fire_dt: 0.1,
+            fire_dt_max: 1.0,
+            fire_alpha: 0.1,
+            fire_n_min: 5,
+            cg_trans_beta: BetaKind::default(),
+            cg_trans_restart: RestartMethod::default(),
+            cg_trans_trial_step: 1E-3,
+            hessian_step: 1E-3,
+            checkpoint_file: None,
+            checkpoint_interval: 1,
+            trajectory_file: None,
         }
     }
 }
 // c38894e0 ends here
+
+// [[file:../dimer.note::6f1d92b4][6f1d92b4]]
+/// The prefix of environment variables for setting `UserOptions`
+const ENV_PREFIX: &str = "DIMER_";
+
+/// Parse the value of environment variable for option `key` as JSON value,
+/// or treat it as plain string if failed or not accepted by the option, such
+/// as a numeric-looking file name.
+fn parse_env_value(key: &str, s: &str) -> serde_json::Value {
+    match serde_json::from_str::<serde_json::Value>(s) {
+        Ok(v) if serde_json::from_value::<UserOptions>(serde_json::json!({ key: v.clone() })).is_ok() => v,
+        _ => serde_json::Value::String(s.to_owned()),
+    }
+}
+
+impl UserOptions {
+    /// Load options from `DIMER_*` environment variables, such as
+    /// `DIMER_MAX_NUM_ROT=10`. Unset options take default values. The loaded
+    /// options will be validated.
+    pub fn from_env() -> Result<Self> {
+        Self::load(None)
+    }
+
+    /// Load options from a config file in JSON or TOML format (by file
+    /// extension). Unset options take default values. The loaded options will
+    /// be validated.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let value = read_config_file(path)?;
+        let vars = parse_options(value)?;
+        vars.validate()?;
+        Ok(vars)
+    }
+
+    /// Load options with the precedence: environment variables > config file
+    /// in `path` > default values. The loaded options will be validated.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        Self::load_with_env(path, std::env::vars())
+    }
+
+    /// Load options as in `load`, but taking environment variables from
+    /// `env_vars`. Unknown `DIMER_*` variables are rejected.
+    pub(crate) fn load_with_env(
+        path: Option<&Path>,
+        env_vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self> {
+        let mut value = match path {
+            Some(path) => read_config_file(path)?,
+            None => serde_json::json!({}),
+        };
+        let map = value
+            .as_object_mut()
+            .ok_or_else(|| DimerError::InvalidOptions(format!("not a table of options: {path:?}")))?;
+
+        let env_vars: HashMap<String, String> = envy::prefixed(ENV_PREFIX)
+            .from_iter(env_vars)
+            .map_err(|e| DimerError::InvalidOptions(format!("invalid environment variables: {e}")))?;
+        let known = serde_json::to_value(Self::default())?;
+        for (k, v) in env_vars {
+            if known.get(&k).is_none() {
+                let msg = format!("unknown option from environment variable: {ENV_PREFIX}{}", k.to_uppercase());
+                return Err(DimerError::InvalidOptions(msg));
+            }
+            debug!("dimer option from environment variable: {k} = {v}");
+            let value = parse_env_value(&k, &v);
+            map.insert(k, value);
+        }
+        let vars = parse_options(value)?;
+        vars.validate()?;
+        Ok(vars)
+    }
+}
+
+fn parse_options(value: serde_json::Value) -> Result<UserOptions> {
+    serde_json::from_value(value).map_err(|e| DimerError::InvalidOptions(e.to_string()))
+}
+
+fn read_config_file(path: &Path) -> Result<serde_json::Value> {
+    let s = gut::fs::read_file(path)?;
+    let value = match path.extension().and_then(|x| x.to_str()) {
+        Some("toml") => toml::from_str(&s).map_err(|e| format!("invalid TOML config file {path:?}: {e}")),
+        _ => serde_json::from_str(&s).map_err(|e| format!("invalid JSON config file {path:?}: {e}")),
+    };
+    value.map_err(DimerError::InvalidOptions)
+}
+// 6f1d92b4 ends here
+
+// [[file:../dimer.note::9a3c5e7f][9a3c5e7f]]
+/// Return `DimerError::InvalidOptions` with formatted message if `cond` is false.
+macro_rules! ensure_option {
+    ($cond:expr, $($arg:tt)*) => {
+        if !$cond {
+            return Err(DimerError::InvalidOptions(format!($($arg)*)));
+        }
+    };
+}
+
+impl UserOptions {
+    /// Check if the options make sense, return error with description if not.
+    pub fn validate(&self) -> Result<()> {
+        ensure_option!(self.fmax > 0.0, "fmax must be positive: {}", self.fmax);
+        ensure_option!(self.distance > 0.0, "dimer distance must be positive: {}", self.distance);
+        ensure_option!(
+            self.trial_rot_angle > 0.0 && self.trial_rot_angle < PI / 2.0,
+            "trial_rot_angle must be in (0, π/2): {}",
+            self.trial_rot_angle
+        );
+        ensure_option!(self.min_rot_angle >= 0.0, "min_rot_angle must not be negative: {}", self.min_rot_angle);
+        ensure_option!(
+            self.min_rot_angle <= self.trial_rot_angle,
+            "min_rot_angle {} is larger than trial_rot_angle {}",
+            self.min_rot_angle,
+            self.trial_rot_angle
+        );
+        ensure_option!(self.max_num_rot > 0, "max_num_rot must be at least 1: {}", self.max_num_rot);
+        ensure_option!(
+            self.lanczos_max_iterations > 0,
+            "lanczos_max_iterations must be at least 1: {}",
+            self.lanczos_max_iterations
+        );
+        ensure_option!(self.saddle_index > 0, "saddle_index must be at least 1: {}", self.saddle_index);
+        ensure_option!(self.max_num_trans > 0, "max_num_trans must be at least 1: {}", self.max_num_trans);
+        ensure_option!(
+            self.max_force_calls != Some(0),
+            "max_force_calls must be at least 1: {:?}",
+            self.max_force_calls
+        );
+        ensure_option!(self.trans_step_size > 0.0, "trans_step_size must be positive: {}", self.trans_step_size);
+        ensure_option!(self.max_trans_step > 0.0, "max_trans_step must be positive: {}", self.max_trans_step);
+        ensure_option!(self.uphill_step_size > 0.0, "uphill_step_size must be positive: {}", self.uphill_step_size);
+        ensure_option!(self.trust_radius > 0.0, "trust_radius must be positive: {}", self.trust_radius);
+        ensure_option!(
+            self.min_trust_radius > 0.0 && self.min_trust_radius <= self.trust_radius,
+            "min_trust_radius must be in (0, trust_radius]: {}",
+            self.min_trust_radius
+        );
+        ensure_option!(self.lbfgs_memory > 0, "lbfgs_memory must be at least 1: {}", self.lbfgs_memory);
+        ensure_option!(
+            self.fire_dt > 0.0 && self.fire_dt <= self.fire_dt_max,
+            "fire_dt must be in (0, fire_dt_max]: {}",
+            self.fire_dt
+        );
+        ensure_option!(
+            self.fire_alpha > 0.0 && self.fire_alpha < 1.0,
+            "fire_alpha must be in (0, 1): {}",
+            self.fire_alpha
+        );
+        ensure_option!(
+            self.cg_trans_trial_step > 0.0,
+            "cg_trans_trial_step must be positive: {}",
+            self.cg_trans_trial_step
+        );
+        ensure_option!(self.hessian_step > 0.0, "hessian_step must be positive: {}", self.hessian_step);
+        ensure_option!(
+            self.checkpoint_interval > 0,
+            "checkpoint_interval must be at least 1: {}",
+            self.checkpoint_interval
+        );
+
+        Ok(())
+    }
+}
+// 9a3c5e7f ends here
@@ -0,0 +1,231 @@
+// [[file:../dimer.note::3f5a8e21][3f5a8e21]]
+//! Analytic model potentials with known stationary points for testing saddle
+//! point searches.
+//!
+//! # References
+//! - Müller, K.; Brown, L. D. Theor. Chim. Acta 1979, 53, 75.
+//! - Jónsson, H.; Mills, G.; Jacobsen, K. W. In Classical and Quantum Dynamics
+//!   in Condensed Phase Simulations; World Scientific, 1998; p 385.
+
+use super::*;
+
+type DMatrix = nalgebra::DMatrix<f64>;
+
+/// Return error if the length of `position` is not `n`.
+fn check_dimension(position: &[f64], n: usize) -> gut::prelude::Result<()> {
+    if position.len() != n {
+        return Err(format_err!("expect {n} coordinates, but found {}", position.len()));
+    }
+    Ok(())
+}
+// 3f5a8e21 ends here
+
+// [[file:../dimer.note::8b6d0f47][8b6d0f47]]
+/// The two-dimensional Müller-Brown potential
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MullerBrown;
+
+impl MullerBrown {
+    /// Parameters A, a, b, c, x0, y0 of the four exponential terms
+    const PARAMS: [[f64; 6]; 4] = [
+        [-200.0, -1.0, 0.0, -10.0, 1.0, 0.0],
+        [-100.0, -1.0, 0.0, -10.0, 0.0, 0.5],
+        [-170.0, -6.5, 11.0, -6.5, -0.5, 1.5],
+        [15.0, 0.7, 0.6, 0.7, -1.0, 1.0],
+    ];
+
+    /// Positions and energies of the two first-order saddle points
+    pub const SADDLES: [([f64; 2], f64); 2] = [
+        ([-0.82200156, 0.62431280], -40.66484),
+        ([0.21248658, 0.29298833], -72.24894),
+    ];
+
+    /// Positions and energies of the three local minima
+    pub const MINIMA: [([f64; 2], f64); 3] = [
+        ([-0.55822363, 1.44172584], -146.69952),
+        ([0.62349940, 0.02803776], -108.16672),
+        ([-0.05001082, 0.46669410], -80.76782),
+    ];
+
+    /// Return energy and gradient at (`x`, `y`).
+    pub fn energy_gradient(x: f64, y: f64) -> (f64, [f64; 2]) {
+        let mut energy = 0.0;
+        let mut gradient = [0.0; 2];
+        for [aa, a, b, c, x0, y0] in Self::PARAMS {
+            let dx = x - x0;
+            let dy = y - y0;
+            let t = aa * (a * dx * dx + b * dx * dy + c * dy * dy).exp();
+            energy += t;
+            gradient[0] += t * (2.0 * a * dx + b * dy);
+            gradient[1] += t * (b * dx + 2.0 * c * dy);
+        }
+        (energy, gradient)
+    }
+}
+
+impl EvaluateEnergyForce for MullerBrown {
+    fn evaluate(&mut self, position: &[f64], force: &mut [f64]) -> gut::prelude::Result<f64> {
+        check_dimension(position, 2)?;
+        let (energy, gradient) = Self::energy_gradient(position[0], position[1]);
+        force[0] = -gradient[0];
+        force[1] = -gradient[1];
+        Ok(energy)
+    }
+}
+// 8b6d0f47 ends here
+
+// [[file:../dimer.note::d41c9b3e][d41c9b3e]]
+/// The two-dimensional LEPS potential coupled with a harmonic oscillator. The
+/// coordinates are the A-B distance and the oscillator coordinate x.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LepsHarmonic;
+
+impl LepsHarmonic {
+    const ALPHA: [f64; 3] = [0.05, 0.30, 0.05];
+    const D: f64 = 4.746;
+    const BETA: f64 = 1.942;
+    const R0: f64 = 0.742;
+    const R_AC: f64 = 3.742;
+    const KC: f64 = 0.2025;
+    const C: f64 = 1.154;
+
+    /// Position and energy of the first-order saddle point
+    pub const SADDLE: ([f64; 2], f64) = ([1.93028658, -0.06841671], -1.03116827);
+
+    /// Positions and energies of the reactant and product minima
+    pub const MINIMA: [([f64; 2], f64); 2] = [
+        ([0.74134584, 1.30362090], -4.50507551),
+        ([3.00096741, -1.30398239], -3.63324694),
+    ];
+
+    /// Coulomb integral and its derivative at distance `r`
+    fn coulomb(r: f64) -> (f64, f64) {
+        let u = (-Self::BETA * (r - Self::R0)).exp();
+        let q = Self::D / 2.0 * (1.5 * u * u - u);
+        let dq = Self::D / 2.0 * Self::BETA * (u - 3.0 * u * u);
+        (q, dq)
+    }
+
+    /// Exchange integral and its derivative at distance `r`
+    fn exchange(r: f64) -> (f64, f64) {
+        let u = (-Self::BETA * (r - Self::R0)).exp();
+        let j = Self::D / 4.0 * (u * u - 6.0 * u);
+        let dj = Self::D / 4.0 * Self::BETA * (6.0 * u - 2.0 * u * u);
+        (j, dj)
+    }
+
+    /// LEPS energy and its derivatives with respect to `r_ab` and `r_bc`
+    fn leps(r_ab: f64, r_bc: f64) -> (f64, f64, f64) {
+        let [a, b, c] = Self::ALPHA.map(|x| 1.0 + x);
+        let r_ac = r_ab + r_bc;
+        let (q_ab, dq_ab) = Self::coulomb(r_ab);
+        let (q_bc, dq_bc) = Self::coulomb(r_bc);
+        let (q_ac, dq_ac) = Self::coulomb(r_ac);
+        let (j_ab, dj_ab) = Self::exchange(r_ab);
+        let (j_bc, dj_bc) = Self::exchange(r_bc);
+        let (j_ac, dj_ac) = Self::exchange(r_ac);
+
+        let s = (j_ab / a).powi(2) + (j_bc / b).powi(2) + (j_ac / c).powi(2)
+            - j_ab * j_bc / (a * b)
+            - j_bc * j_ac / (b * c)
+            - j_ab * j_ac / (a * c);
+        let ds_ab = 2.0 * j_ab / (a * a) - j_bc / (a * b) - j_ac / (a * c);
+        let ds_bc = 2.0 * j_bc / (b * b) - j_ab / (a * b) - j_ac / (b * c);
+        let ds_ac = 2.0 * j_ac / (c * c) - j_bc / (b * c) - j_ab / (a * c);
+        let sq = s.sqrt();
+
+        let energy = q_ab / a + q_bc / b + q_ac / c - sq;
+        let d_ac = dq_ac / c - ds_ac * dj_ac / (2.0 * sq);
+        let d_ab = dq_ab / a - ds_ab * dj_ab / (2.0 * sq) + d_ac;
+        let d_bc = dq_bc / b - ds_bc * dj_bc / (2.0 * sq) + d_ac;
+        (energy, d_ab, d_bc)
+    }
+
+    /// Return energy and gradient at A-B distance `r_ab` and oscillator
+    /// coordinate `x`.
+    pub fn energy_gradient(r_ab: f64, x: f64) -> (f64, [f64; 2]) {
+        let (e, d_ab, d_bc) = Self::leps(r_ab, Self::R_AC - r_ab);
+        let d = r_ab - (Self::R_AC / 2.0 - x / Self::C);
+        let energy = e + 2.0 * Self::KC * d * d;
+        let gradient = [d_ab - d_bc + 4.0 * Self::KC * d, 4.0 * Self::KC * d / Self::C];
+        (energy, gradient)
+    }
+}
+
+impl EvaluateEnergyForce for LepsHarmonic {
+    fn evaluate(&mut self, position: &[f64], force: &mut [f64]) -> gut::prelude::Result<f64> {
+        check_dimension(position, 2)?;
+        let (energy, gradient) = Self::energy_gradient(position[0], position[1]);
+        force[0] = -gradient[0];
+        force[1] = -gradient[1];
+        Ok(energy)
+    }
+}
+// d41c9b3e ends here
+
+// [[file:../dimer.note::6ae27f93][6ae27f93]]
+/// Quadratic potential E = ½ (x - x0)ᵀ H (x - x0) in arbitrary dimensions. It
+/// is a first-order saddle at x0 if the Hessian H has exactly one negative
+/// eigenvalue.
+#[derive(Debug, Clone)]
+pub struct QuadraticSaddle {
+    saddle: DVector,
+    hessian: DMatrix,
+}
+
+impl QuadraticSaddle {
+    /// Construct the potential from `saddle` position and symmetric `hessian`
+    /// in row-major order.
+    pub fn new(saddle: &[f64], hessian: &[f64]) -> Result<Self> {
+        let n = saddle.len();
+        if hessian.len() != n * n {
+            return Err(DimerError::InvalidInput(format!(
+                "expect {} elements for {n}x{n} hessian, but found {}",
+                n * n,
+                hessian.len()
+            )));
+        }
+        let hessian = DMatrix::from_row_slice(n, n, hessian);
+        if (&hessian - hessian.transpose()).amax() > 1E-8 {
+            return Err(DimerError::InvalidInput("hessian is not symmetric".into()));
+        }
+        let saddle = saddle.to_vector();
+        Ok(Self { saddle, hessian })
+    }
+
+    /// Construct the potential with a diagonal Hessian from `curvatures`.
+    pub fn from_curvatures(saddle: &[f64], curvatures: &[f64]) -> Result<Self> {
+        let n = curvatures.len();
+        let mut hessian = vec![0.0; n * n];
+        for (i, c) in curvatures.iter().enumerate() {
+            hessian[i * n + i] = *c;
+        }
+        Self::new(saddle, &hessian)
+    }
+
+    /// The position of the stationary point.
+    pub fn saddle(&self) -> &[f64] {
+        self.saddle.as_slice()
+    }
+
+    /// Return the lowest curvature and its normalized mode of the Hessian.
+    pub fn lowest_mode(&self) -> (f64, Vec<f64>) {
+        let eigen = self.hessian.clone().symmetric_eigen();
+        let i = eigen.eigenvalues.imin();
+        let mode = eigen.eigenvectors.column(i).iter().copied().collect();
+        (eigen.eigenvalues[i], mode)
+    }
+}
+
+impl EvaluateEnergyForce for QuadraticSaddle {
+    fn evaluate(&mut self, position: &[f64], force: &mut [f64]) -> gut::prelude::Result<f64> {
+        check_dimension(position, self.saddle.len())?;
+        let dx = position.to_vector() - &self.saddle;
+        let g = &self.hessian * &dx;
+        for (f, g) in force.iter_mut().zip(g.iter()) {
+            *f = -g;
+        }
+        Ok(0.5 * dx.dot(&g))
+    }
+}
+// 6ae27f93 ends here
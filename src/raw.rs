@@ -1,5 +1,5 @@
 // [[file:../dimer.note::d5c73cde][d5c73cde]]
-fn compute_dimer_endpoints(r0: &DVector, dr: f64, n: &DVector) -> [DVector; 2] {
+pub(crate) fn compute_dimer_endpoints(r0: &DVector, dr: f64, n: &DVector) -> [DVector; 2] {
     let r1 = r0 + dr * n;
     let r2 = r0 - dr * n;
     [r1, r2]
@@ -18,6 +18,12 @@ fn compute_rotational_force(f0: &DVector, f1: &DVector, dr: f64) -> DVector {
 }
 // 68051c57 ends here
 
+// [[file:../dimer.note::e2b94c1a][e2b94c1a]]
+fn compute_rotational_force_central(f2: &DVector, f1: &DVector, dr: f64) -> DVector {
+    (f1 - f2) / (2.0 * dr)
+}
+// e2b94c1a ends here
+
 // [[file:../dimer.note::746f3305][746f3305]]
 fn compute_dimer_axis(r0: &DVector, r1: &DVector) -> DVector {
     (r1 - r0).normalize()
@@ -48,7 +54,8 @@ use super::*;
 // de6e084c ends here
 
 // [[file:../dimer.note::4648b13c][4648b13c]]
-/// Represents a raw dimer with a center `0` and an endpoint `1`
+/// Represents a raw dimer with a center `0` and an endpoint `1`, and
+/// optionally the other endpoint `2` for central differencing.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct RawDimer {
     /// Postions of image 0 in dimer
@@ -59,6 +66,10 @@ pub struct RawDimer {
     pub r1: DVector,
     /// Forces of image 1 in dimer
     pub f1: DVector,
+    /// Forces of image 2 in dimer, which is located at `2 * r0 - r1`. If
+    /// available, second derivatives will be estimated by central differences.
+    #[serde(default)]
+    pub f2: Option<DVector>,
 }
 // 4648b13c ends here
 
@@ -96,6 +107,12 @@ impl RotationState {
     pub fn curvature_mode(&self) -> &DVector {
         &self.n
     }
+
+    /// Constrain the rotational force using `project`, such as removing
+    /// components of frozen coordinates.
+    pub(crate) fn constrain_rotational_force(&mut self, project: impl Fn(&DVector) -> DVector) {
+        self.fr = project(&self.fr);
+    }
 }
 // 62d61ee4 ends here
 
@@ -104,7 +121,10 @@ impl RawDimer {
     /// Estimate second derivative information at dimer center using finite differencing
     pub fn extrapolate(&self) -> RotationState {
         let dr = (&self.r1 - &self.r0).norm();
-        let fr = compute_rotational_force(&self.f0, &self.f1, dr);
+        let fr = match &self.f2 {
+            Some(f2) => compute_rotational_force_central(f2, &self.f1, dr),
+            None => compute_rotational_force(&self.f0, &self.f1, dr),
+        };
         let n = self.dimer_axis();
         let cx = compute_dimer_curvature(&fr, &n);
         RotationState { fr, n, cx }
@@ -114,5 +134,27 @@ impl RawDimer {
     pub fn dimer_axis(&self) -> DVector {
         compute_dimer_axis(&self.r0, &self.r1)
     }
+
+    /// Return positions of dimer endpoint `2`.
+    pub fn endpoint2(&self) -> DVector {
+        let dr = (&self.r1 - &self.r0).norm();
+        let [_, r2] = compute_dimer_endpoints(&self.r0, dr, &self.dimer_axis());
+        r2
+    }
+
+    /// Return the discrepancy between curvatures estimated by forward and
+    /// central differences as an accuracy estimate. Return None if forces of
+    /// endpoint `2` are not available.
+    pub fn curvature_error(&self) -> Option<f64> {
+        let f2 = self.f2.as_ref()?;
+        let dr = (&self.r1 - &self.r0).norm();
+        let n = self.dimer_axis();
+        // forward difference is equivalent to central difference with
+        // extrapolated force on endpoint 2
+        let f2_forward = compute_dimer_endpoint2_force(&self.f0, &self.f1);
+        let c_forward = compute_dimer_curvature(&compute_rotational_force_central(&f2_forward, &self.f1, dr), &n);
+        let c_central = compute_dimer_curvature(&compute_rotational_force_central(f2, &self.f1, dr), &n);
+        Some((c_forward - c_central).abs())
+    }
 }
 // 02a5a92f ends here
@@ -0,0 +1,64 @@
+// [[file:../dimer.note::6e0c2b85][6e0c2b85]]
+use super::*;
+// 6e0c2b85 ends here
+
+// [[file:../dimer.note::a91f4d3c][a91f4d3c]]
+/// Return orthonormal basis vectors of rigid-body translations and rotations
+/// for atoms in Cartesian `positions`. There are six rigid-body modes in
+/// general, and five for linear molecules. The modes are transformed into
+/// mass-weighted coordinates if `sqrt_masses` is provided.
+pub(crate) fn rigid_body_modes(positions: &[f64], sqrt_masses: Option<&DVector>) -> Vec<DVector> {
+    let n = positions.len();
+    let coords = positions.as_3d();
+    let natoms = coords.len() as f64;
+    let mut center = [0.0; 3];
+    for p in coords {
+        for (c, x) in center.iter_mut().zip(p) {
+            *c += x / natoms;
+        }
+    }
+
+    let mut candidates = vec![];
+    // overall translations
+    for k in 0..3 {
+        candidates.push(DVector::from_fn(n, |i, _| if i % 3 == k { 1.0 } else { 0.0 }));
+    }
+    // overall rotations around x, y and z axes
+    for k in 0..3 {
+        let mut v = DVector::zeros(n);
+        for (i, p) in coords.iter().enumerate() {
+            let r = [p[0] - center[0], p[1] - center[1], p[2] - center[2]];
+            // cross product: e_k × r
+            let (a, b) = ((k + 1) % 3, (k + 2) % 3);
+            v[3 * i + b] = r[a];
+            v[3 * i + a] = -r[b];
+        }
+        candidates.push(v);
+    }
+
+    if let Some(sqrt_m) = sqrt_masses {
+        candidates.iter_mut().for_each(|v| v.component_mul_assign(sqrt_m));
+    }
+
+    // Gram-Schmidt orthonormalization, dropping linear dependent modes
+    let mut modes: Vec<DVector> = vec![];
+    for v in candidates {
+        let norm0 = v.norm();
+        let v = project_out(&v, &modes);
+        let norm = v.norm();
+        if norm0 > 0.0 && norm > 1E-6 * norm0 {
+            modes.push(v / norm);
+        }
+    }
+    modes
+}
+
+/// Remove components along orthonormal vectors in `modes` from `v`.
+pub(crate) fn project_out(v: &DVector, modes: &[DVector]) -> DVector {
+    let mut v = v.clone();
+    for m in modes {
+        v -= v.dot(m) * m;
+    }
+    v
+}
+// a91f4d3c ends here
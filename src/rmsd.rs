@@ -0,0 +1,87 @@
+// [[file:../dimer.note::61d8f4a2][61d8f4a2]]
+//! RMSD between structures after optimal superposition
+//!
+//! # References
+//! - Kabsch, W. Acta Cryst. 1976, A32, 922.
+
+use super::*;
+
+type Matrix3 = nalgebra::Matrix3<f64>;
+type Vector3 = nalgebra::Vector3<f64>;
+// 61d8f4a2 ends here
+
+// [[file:../dimer.note::0f7c3b69][0f7c3b69]]
+/// Return coordinates relative to their centroid, and the centroid.
+fn centered(coords: &[[f64; 3]]) -> (Vec<Vector3>, Vector3) {
+    let points = coords.iter().map(|&p| Vector3::from(p)).collect_vec();
+    let centroid = points.iter().sum::<Vector3>() / points.len() as f64;
+    (points.into_iter().map(|p| p - centroid).collect(), centroid)
+}
+
+/// Superpose `mobile` onto `target` by Kabsch algorithm, and return the
+/// transformed coordinates of `mobile`.
+fn superpose(mobile: &[[f64; 3]], target: &[[f64; 3]]) -> Vec<[f64; 3]> {
+    let (p, _) = centered(mobile);
+    let (q, cq) = centered(target);
+    let h = p.iter().zip(&q).fold(Matrix3::zeros(), |acc, (p, q)| acc + p * q.transpose());
+    let svd = h.svd(true, true);
+    let u = svd.u.expect("svd with u");
+    let v = svd.v_t.expect("svd with v_t").transpose();
+    // avoid improper rotation (reflection)
+    let d = (v * u.transpose()).determinant().signum();
+    let r = v * Matrix3::from_diagonal(&Vector3::new(1.0, 1.0, d)) * u.transpose();
+    p.iter().map(|p| (r * p + cq).into()).collect()
+}
+
+fn rmsd(a: &[[f64; 3]], b: &[[f64; 3]]) -> f64 {
+    let sd: f64 = a
+        .iter()
+        .zip(b)
+        .map(|(a, b)| (0..3).map(|k| (a[k] - b[k]).powi(2)).sum::<f64>())
+        .sum();
+    (sd / a.len() as f64).sqrt()
+}
+
+/// Return the permutation of atoms in `mobile` matching nearest atoms with
+/// the same element in `target` greedily.
+fn greedy_permutation(mobile: &[[f64; 3]], target: &[[f64; 3]], elements: &[String]) -> Result<Vec<usize>> {
+    let n = target.len();
+    let mut used = vec![false; n];
+    let mut perm = Vec::with_capacity(n);
+    for (i, t) in target.iter().enumerate() {
+        let j = (0..n)
+            .filter(|&j| !used[j] && elements[j] == elements[i])
+            .min_by(|&j, &k| rmsd(&[mobile[j]], &[*t]).total_cmp(&rmsd(&[mobile[k]], &[*t])))
+            .ok_or_else(|| DimerError::InvalidInput(format!("no unmatched atom of element {} left", elements[i])))?;
+        used[j] = true;
+        perm.push(j);
+    }
+    Ok(perm)
+}
+
+/// Return RMSD between atom positions `a` and `b` after optimal
+/// superposition. If `elements` is provided, atoms with the same element are
+/// allowed to be permuted, using a greedy nearest-atom assignment which is
+/// approximate.
+pub(crate) fn aligned_rmsd(a: &[f64], b: &[f64], elements: Option<&[String]>) -> Result<f64> {
+    let (a, b) = (a.as_3d(), b.as_3d());
+    let mut best = rmsd(&superpose(b, a), a);
+    if let Some(elements) = elements {
+        if elements.len() != a.len() {
+            return Err(DimerError::InvalidInput(format!(
+                "expect {} elements, but found {}",
+                a.len(),
+                elements.len()
+            )));
+        }
+        let mut b = b.to_vec();
+        for _ in 0..3 {
+            let aligned = superpose(&b, a);
+            let perm = greedy_permutation(&aligned, a, elements)?;
+            b = perm.iter().map(|&j| b[j]).collect();
+            best = best.min(rmsd(&superpose(&b, a), a));
+        }
+    }
+    Ok(best)
+}
+// 0f7c3b69 ends here
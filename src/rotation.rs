@@ -2,38 +2,94 @@
 use super::*;
 
 use crate::cg::CG;
+use crate::lbfgs::Lbfgs;
 // 875f7ef9 ends here
 
 // [[file:../dimer.note::1b911cfd][1b911cfd]]
 impl<'a> Dimer<'a> {
-    /// Rebuild `RawDimer` from start (updating center and endpoint 1)
-    fn reinitialize(&mut self) -> Result<(RawDimer, f64)> {
+    /// Rebuild `RawDimer` from start (updating center and endpoint 1). The
+    /// forces and energy at dimer center are reused from `center` if they
+    /// have been evaluated already.
+    pub(crate) fn reinitialize(&mut self, center: Option<(&DVector, f64)>) -> Result<(RawDimer, f64)> {
+        // refresh rigid-body modes for the current center geometry
+        self.update_rigid_body_modes()?;
         let dr = self.vars.distance;
         let r0 = self.center.clone();
-        let r1 = &r0 + dr * &self.orientation;
-
-        self.dynamics.set_position(r0.as_slice());
-        let f0 = self.dynamics.get_force()?.to_vector();
-        let e0 = self.dynamics.get_energy()?;
-        self.dynamics.set_position(r1.as_slice());
-        let f1 = self.dynamics.get_force()?.to_vector();
-        let raw_dimer = RawDimer { r0, r1, f0, f1 };
+        let [r1, r2] = compute_dimer_endpoints(&r0, dr, &self.orientation);
+
+        let (f0, e0) = match center {
+            Some((f0, e0)) => (f0.clone(), e0),
+            None => {
+                let f0 = self.compute_force_at(&r0, ForcePhase::Reinitialization)?;
+                (f0, self.compute_energy()?)
+            }
+        };
+        let f1 = self.compute_force_at(&r1, ForcePhase::Reinitialization)?;
+        let f2 = if self.vars.use_central_difference {
+            Some(self.compute_force_at(&r2, ForcePhase::Reinitialization)?)
+        } else {
+            None
+        };
+        let raw_dimer = RawDimer { r0, r1, f0, f1, f2 };
         Ok((raw_dimer, e0))
     }
 }
 // 1b911cfd ends here
 
 // [[file:../dimer.note::5bff1ad1][5bff1ad1]]
+/// History data for determining rotational direction in successive rotation
+/// iterations
+#[derive(Debug, Clone)]
+struct RotationHistory {
+    cg: CG,
+    lbfgs: Lbfgs,
+}
+
+impl RotationHistory {
+    fn new(vars: &UserOptions) -> Self {
+        Self {
+            cg: CG::default(),
+            lbfgs: Lbfgs::new(vars.lbfgs_memory, 1.0),
+        }
+    }
+}
+
 impl<'a> Dimer<'a> {
     // update rotational direction perpendicular to dimer orientation.
-    // use conjugate-gradient or steepest descent to determinte rotational direction
-    fn get_rotational_direction(&mut self, f_rot: &DVector, cg: &mut crate::cg::CG) -> DVector {
+    // use L-BFGS, conjugate-gradient or steepest descent to determinte rotational direction
+    fn get_rotational_direction(&mut self, f_rot: &DVector, history: &mut RotationHistory) -> Result<DVector> {
         let tau = &self.orientation;
-        if self.vars.use_cg_rot {
-            cg.propagate_dimer(f_rot, Some(tau)).normalize()
+        let theta = if self.vars.use_lbfgs_rot {
+            // L-BFGS on the hypersphere of dimer orientation (Kastner2008JCP):
+            // work with the rotational force in the tangent space
+            let f_perp = f_rot.vector_rejection(tau);
+            let mut theta = history.lbfgs.propagate(tau, &f_perp).vector_rejection(tau);
+            if theta.dot(&f_perp) <= 0.0 {
+                warn!("L-BFGS rotational direction is uphill; reset history");
+                history.lbfgs.reset();
+                theta = history.lbfgs.propagate(tau, &f_perp).vector_rejection(tau);
+            }
+            theta
+        } else if self.vars.use_cg_rot {
+            history.cg.propagate_dimer(f_rot, Some(tau))?
         } else {
-            f_rot.vector_rejection(tau).normalize()
+            f_rot.vector_rejection(tau)
+        };
+        // keep frozen coordinates fixed in rotation
+        let theta = self.constrain_mode(&theta);
+        // avoid NaN direction from normalizing a zero vector
+        if theta.norm() == 0.0 {
+            return Err(DimerError::ZeroRotationalForce);
         }
+        Ok(theta.normalize())
+    }
+
+    /// Estimate second derivative information at dimer center with
+    /// constraints applied on rotational force.
+    fn rotation_state(&self, raw_dimer: &RawDimer) -> RotationState {
+        let mut state = raw_dimer.extrapolate();
+        state.constrain_rotational_force(|f| self.constrain_mode(f));
+        state
     }
 }
 // 5bff1ad1 ends here
@@ -56,7 +112,8 @@ fn check_dimer_rotation_convergence(phi_est: f64, phi_tol: f64) -> bool {
 // [[file:../dimer.note::69cb7fbe][69cb7fbe]]
 impl<'a> Dimer<'a> {
     /// Estimate optimal rotation by rotating the dimer in direction `theta`
-    /// with trial angle `phi1` using Fourier transform.
+    /// with trial angle `phi1` using Fourier transform. Return the optimal
+    /// rotation angle and the estimated curvature.
     ///
     /// # Parameters
     ///
@@ -64,12 +121,25 @@ impl<'a> Dimer<'a> {
     /// * theta: rotation direction
     /// * phi1: trial rotation angle
     ///
-    fn rotate_dimer_within(&mut self, raw_dimer: &mut RawDimer, theta: &DVector, phi1: f64, phi_est: f64) -> Result<f64> {
+    fn rotate_dimer_within(
+        &mut self,
+        raw_dimer: &mut RawDimer,
+        theta: &DVector,
+        phi1: f64,
+        phi_est: f64,
+    ) -> Result<(f64, f64)> {
         // get endpoint 1 (R1, F1) after trial rotation
         let r1_prime = raw_dimer.get_endpoint1_after_rotation(&self.orientation, &theta, phi1);
-        self.dynamics.set_position(r1_prime.as_slice());
-        let f1_prime = self.dynamics.get_force()?.to_vector();
-        let fourier_state = raw_dimer.fourier_rotate(r1_prime, f1_prime, phi1, theta, self.vars.use_extrapolated_force);
+        let f1_prime = self.compute_force_at(&r1_prime, ForcePhase::Rotation)?;
+        let extrapolated = self.vars.use_extrapolated_force;
+        let fourier_state = if self.vars.use_central_difference {
+            // get endpoint 2 (R2, F2) after trial rotation
+            let r2_prime = 2.0 * &raw_dimer.r0 - &r1_prime;
+            let f2_prime = self.compute_force_at(&r2_prime, ForcePhase::Rotation)?;
+            raw_dimer.fourier_rotate_central(r1_prime, f1_prime, f2_prime, phi1, theta, extrapolated)
+        } else {
+            raw_dimer.fourier_rotate(r1_prime, f1_prime, phi1, theta, extrapolated)
+        };
         let phi_min = fourier_state.phi_min;
         let curvature_min = fourier_state.curvature_min;
         info!(
@@ -86,8 +156,9 @@ impl<'a> Dimer<'a> {
 
         raw_dimer.r1 = fourier_state.r1_min;
         raw_dimer.f1 = fourier_state.f1_min;
+        raw_dimer.f2 = fourier_state.f2_min;
 
-        Ok(curvature_min)
+        Ok((phi_min, curvature_min))
     }
 }
 // 69cb7fbe ends here
@@ -103,20 +174,38 @@ pub struct RotationOutput {
     pub curvature_min: f64,
     /// The number of iterations used in rotation step
     pub n_iterations: usize,
+    /// The discrepancy between curvatures from forward and central
+    /// differences, available only in central difference mode.
+    pub curvature_error: Option<f64>,
 }
 
 /// The part for DIMER rotation
 impl<'a> Dimer<'a> {
+    /// Find the lowest curvature mode at dimer center using dimer rotations
+    /// or Lanczos iterations if `use_lanczos` is enabled. The forces and
+    /// energy at dimer center are reused from `center` if available.
+    pub(crate) fn next_minimum_mode_step(&mut self, center: Option<(&DVector, f64)>) -> Result<RotationOutput> {
+        if self.vars.use_lanczos {
+            self.next_lanczos_step(self.vars.lanczos_max_iterations, center)
+        } else {
+            self.next_rotation_step(self.vars.max_num_rot, center)
+        }
+    }
+
     /// Rotate the dimer axis into the lowest curvature mode of the potential
     /// energy at the dimer center estimated in Fourier series.
-    pub(crate) fn next_rotation_step(&mut self, n_max_rot: usize) -> Result<RotationOutput> {
+    pub(crate) fn next_rotation_step(
+        &mut self,
+        n_max_rot: usize,
+        center: Option<(&DVector, f64)>,
+    ) -> Result<RotationOutput> {
         let tau_ini = self.orientation.clone();
         let phi_tol = self.vars.min_rot_angle;
 
-        let mut cg = CG::default();
-        let (mut raw_dimer, e0) = self.reinitialize()?;
+        let mut history = RotationHistory::new(&self.vars);
+        let (mut raw_dimer, e0) = self.reinitialize(center)?;
         // save the state before trial rotation
-        let mut state = raw_dimer.extrapolate();
+        let mut state = self.rotation_state(&raw_dimer);
         let mut curvature_min = state.curvature();
         let mut niter = 0;
         loop {
@@ -135,6 +224,10 @@ impl<'a> Dimer<'a> {
                     warn!("Max allowed iterations {n_max_rot} reached, but dimer rotation not converged yet.");
                     break;
                 }
+                (false, false) if self.budget_exhausted() => {
+                    warn!("Budget of force calls exhausted, but dimer rotation not converged yet.");
+                    break;
+                }
                 (false, false) => {}
             }
 
@@ -146,35 +239,59 @@ impl<'a> Dimer<'a> {
             };
             // rotate `raw_dimer` in optimal direction with a angle leading to lowest curvature
             let f_rot = state.rotational_force();
-            assert!(f_rot.norm() > 0.0, "invalid rotational force: {:?}", &f_rot);
-            let theta = self.get_rotational_direction(f_rot, &mut cg);
-            let curvature_min_est = self.rotate_dimer_within(&mut raw_dimer, &theta, phi1, phi_est)?;
+            if f_rot.norm() == 0.0 {
+                return Err(DimerError::ZeroRotationalForce);
+            }
+            let theta = self.get_rotational_direction(f_rot, &mut history)?;
+            let (phi_min, curvature_min_est) = self.rotate_dimer_within(&mut raw_dimer, &theta, phi1, phi_est)?;
             // Update extrapolated force of endpint `1` if necessary
             if !self.vars.use_extrapolated_force {
-                self.dynamics.set_position(raw_dimer.r1.as_slice());
-                let f1 = self.dynamics.get_force()?.to_vector();
+                let f1 = self.compute_force_at(&raw_dimer.r1, ForcePhase::Rotation)?;
                 let s = f1.cosine_similarity(&raw_dimer.f1);
                 debug!("similarity between extrapolated force and real force of endpoint 1: {}", s);
                 raw_dimer.f1 = f1;
+                if raw_dimer.f2.is_some() {
+                    let r2 = raw_dimer.endpoint2();
+                    raw_dimer.f2 = Some(self.compute_force_at(&r2, ForcePhase::Rotation)?);
+                }
             }
             // Update dimer state after rotation
-            state = raw_dimer.extrapolate();
+            state = self.rotation_state(&raw_dimer);
             // Update current dimer orientation, important for translation step
-            self.orientation = state.curvature_mode().clone();
+            self.set_orientation(state.curvature_mode().clone())?;
             // Recalculate curvature. If we do not use extrapolated f1, the
             // curvature_min should be updated with more accurate number
             curvature_min = state.curvature();
             debug!("real curvature vs estimated curvature: {curvature_min} vs. {curvature_min_est}");
+            let rotation_angle = self.orientation.cosine_similarity(&tau_ini).acos();
+            self.record(IterationRecord {
+                kind: IterationKind::Rotation,
+                iteration: niter,
+                energy: e0,
+                curvature_est: Some(curvature_min_est),
+                curvature: curvature_min,
+                phi_est: Some(phi_est),
+                phi_min: Some(phi_min),
+                fmax: None,
+                rotation_angle: Some(rotation_angle),
+                step_length: None,
+                force_calls: self.ncalls.total(),
+            })?;
         }
         // Total rotation angle during rotation steps
         let phi = self.orientation.cosine_similarity(&tau_ini).acos().to_degrees();
         info!("Total rotational angle = {phi:.2}°; optimized curvature = {curvature_min}");
+        let curvature_error = raw_dimer.curvature_error();
+        if let Some(err) = curvature_error {
+            info!("curvature discrepancy between forward and central differences: {err}");
+        }
 
         let out = RotationOutput {
             raw_dimer,
             curvature_min,
             energy: e0,
             n_iterations: niter,
+            curvature_error,
         };
         Ok(out)
     }
@@ -0,0 +1,203 @@
+// [[file:../dimer.note::3a1f0c7e][3a1f0c7e]]
+use super::*;
+
+use crate::translation::TranslationOptimizer;
+// 3a1f0c7e ends here
+
+// [[file:../dimer.note::b7e2d4a9][b7e2d4a9]]
+/// The termination status of DIMER saddle point search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SearchStatus {
+    /// Converged to a saddle point within `fmax`
+    Converged,
+    /// Max allowed translation steps reached before convergence
+    MaxIterationsReached,
+    /// The budget of force calls exhausted before convergence
+    BudgetExhausted,
+}
+
+/// Results of a full DIMER saddle point search
+#[derive(Debug, Clone)]
+pub struct SearchOutput {
+    /// The termination status of the search
+    pub status: SearchStatus,
+    /// The number of translation iterations used
+    pub n_iterations: usize,
+    /// The total number of rotation iterations used
+    pub n_rotations: usize,
+    /// The number of force calls in each phase
+    pub force_calls: ForceCalls,
+    /// The results at final dimer center, which is the saddle point if converged.
+    pub saddle: DimerOutput,
+}
+
+impl SearchOutput {
+    /// Return true if the saddle point search converged.
+    pub fn converged(&self) -> bool {
+        self.status == SearchStatus::Converged
+    }
+
+    /// Return error `DimerError::NotConverged` if the saddle point search not
+    /// converged.
+    pub fn ensure_converged(self) -> Result<Self> {
+        if self.converged() {
+            Ok(self)
+        } else {
+            Err(DimerError::NotConverged {
+                status: self.status,
+                n_iterations: self.n_iterations,
+            })
+        }
+    }
+}
+// b7e2d4a9 ends here
+
+// [[file:../dimer.note::d80f3b6e][d80f3b6e]]
+/// The state of DIMER saddle point search, which can be saved for restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SearchState {
+    /// The number of translation iterations done
+    pub n_iterations: usize,
+    /// The total number of rotation iterations done
+    pub n_rotations: usize,
+    /// Translation optimizer with history data
+    pub optimizer: TranslationOptimizer,
+    /// The energy at dimer center in last iteration, before its translation step
+    pub energy: Option<f64>,
+    /// The real forces at dimer center in last iteration, before its translation step
+    pub forces: Option<DVector>,
+}
+
+impl SearchState {
+    pub fn new(vars: &UserOptions) -> Self {
+        Self {
+            n_iterations: 0,
+            n_rotations: 0,
+            optimizer: TranslationOptimizer::new(vars),
+            energy: None,
+            forces: None,
+        }
+    }
+}
+// d80f3b6e ends here
+
+// [[file:../dimer.note::5c9e61d2][5c9e61d2]]
+/// A saddle point is found only when the real forces are small enough and the
+/// dimer sits in a region of negative curvature.
+fn check_dimer_translation_convergence(fmax: f64, fmax_tol: f64, c_min: f64) -> bool {
+    if fmax < fmax_tol && c_min.is_sign_negative() {
+        info!("dimer translation converged: fmax = {fmax:.4} < {fmax_tol:.4}; curvature = {c_min:.4}");
+        return true;
+    }
+
+    false
+}
+// 5c9e61d2 ends here
+
+// [[file:../dimer.note::e41b8f07][e41b8f07]]
+/// The part for full DIMER saddle point search
+impl<'a> Dimer<'a> {
+    /// Search for a saddle point by repeating dimer rotation and translation
+    /// until `fmax` is met, `max_num_trans` steps reached, or the budget of
+    /// `max_force_calls` used up. The dimer center will be moved to the final
+    /// geometry.
+    pub fn search(&mut self) -> Result<SearchOutput> {
+        self.vars.validate()?;
+        let n_max_trans = self.vars.max_num_trans;
+        // continue from restarted state if any
+        let mut state = match self.state.take() {
+            Some(state) => state,
+            None => {
+                self.reset_trajectory()?;
+                SearchState::new(&self.vars)
+            }
+        };
+        loop {
+            state.n_iterations += 1;
+            let niter = state.n_iterations;
+            info!("dimer translation iteration {niter}");
+            let rotation = self.next_minimum_mode_step(None)?;
+            let raw_dimer = &rotation.raw_dimer;
+            let c_min = rotation.curvature_min;
+            let modes = self.next_saddle_modes_step(&raw_dimer.f0, rotation.energy, c_min)?;
+            // all inverted modes should have negative curvatures at saddle point
+            let c_max = modes.iter().fold(c_min, |acc, m| acc.max(m.curvature));
+            let effective_force = self.next_translation_step(raw_dimer, c_min);
+            state.n_rotations += rotation.n_iterations;
+            state.energy = Some(rotation.energy);
+            state.forces = Some(raw_dimer.f0.clone());
+
+            // forces on frozen coordinates are irrelevant for convergence
+            let fmax = self.force_to_cartesian(&self.constrain(&raw_dimer.f0)).amax();
+            info!("{:^15}{:^15}{:^15}", "energy", "fmax", "curvature");
+            info!("{:^-15.5}{:^15.4}{:^-15.4}", rotation.energy, fmax, c_min);
+            let converged = check_dimer_translation_convergence(fmax, self.vars.fmax, c_max);
+            let status = match (converged, niter >= n_max_trans) {
+                (true, _) => Some(SearchStatus::Converged),
+                (false, _) if self.budget_exhausted() => {
+                    warn!("Budget of {} force calls exhausted, but saddle point not found yet.", self.ncalls.total());
+                    Some(SearchStatus::BudgetExhausted)
+                }
+                (false, true) => {
+                    warn!("Max allowed iterations {n_max_trans} reached, but saddle point not found yet.");
+                    Some(SearchStatus::MaxIterationsReached)
+                }
+                (false, false) => None,
+            };
+
+            let mut record = IterationRecord {
+                kind: IterationKind::Translation,
+                iteration: niter,
+                energy: rotation.energy,
+                curvature_est: None,
+                curvature: c_min,
+                phi_est: None,
+                phi_min: None,
+                fmax: Some(self.force_to_cartesian(&effective_force).amax()),
+                rotation_angle: None,
+                step_length: None,
+                force_calls: self.ncalls.total(),
+            };
+            if let Some(status) = status {
+                self.record(record)?;
+                // keep positions of `Dynamics` at dimer center
+                self.sync_dynamics();
+                let saddle = self.output(&rotation, &effective_force, modes);
+                info!("dimer search done in {niter} translation and {} rotation iterations", state.n_rotations);
+                return Ok(SearchOutput {
+                    status,
+                    n_iterations: niter,
+                    n_rotations: state.n_rotations,
+                    force_calls: self.ncalls,
+                    saddle,
+                });
+            }
+
+            let displacement = self.next_translation_displacement(
+                &mut state.optimizer,
+                raw_dimer,
+                &effective_force,
+                c_max,
+                rotation.energy,
+            )?;
+            self.translate(&displacement);
+            record.step_length = Some(displacement.norm());
+            record.force_calls = self.ncalls.total();
+            self.record(record)?;
+
+            if let Some(path) = &self.vars.checkpoint_file {
+                if niter % self.vars.checkpoint_interval.max(1) == 0 {
+                    self.checkpoint(&state).save(path)?;
+                }
+            }
+        }
+    }
+
+    /// Move dimer center by `displacement`, keeping positions of `Dynamics` in sync.
+    fn translate(&mut self, displacement: &DVector) {
+        let displacement = self.constrain(displacement);
+        self.center += displacement;
+        self.sync_dynamics();
+    }
+}
+// e41b8f07 ends here
@@ -21,7 +21,7 @@ fn get_raw_dimer() -> Result<RawDimer> {
     let f1 = raw_data.f1.to_vector();
     let tau = &r1 - &r0;
     let dr = raw_data.dr;
-    let raw_dimer = RawDimer { r0, r1, f0, f1 };
+    let raw_dimer = RawDimer { r0, r1, f0, f1, f2: None };
     Ok(raw_dimer)
 }
 // 917f277b ends here
@@ -98,3 +98,349 @@ fn test_fourier_rotation() -> Result<()> {
     Ok(())
 }
 // 170e45af ends here
+
+// [[file:../dimer.note::9e5a0c34][9e5a0c34]]
+#[test]
+fn test_lbfgs_quadratic() -> Result<()> {
+    use crate::lbfgs::Lbfgs;
+
+    // E = 0.5 * x^T A x with a diagonal A
+    let a = [1.0, 4.0, 9.0].to_vector();
+    let mut x = [1.0, -1.0, 0.5].to_vector();
+    let mut lbfgs = Lbfgs::new(5, 0.1);
+    for _ in 0..50 {
+        let f = -a.component_mul(&x);
+        if f.amax() < 1e-8 {
+            break;
+        }
+        x += lbfgs.propagate(&x, &f);
+    }
+    approx::assert_relative_eq!(x, [0.0, 0.0, 0.0].to_vector(), epsilon = 1e-6);
+
+    Ok(())
+}
+// 9e5a0c34 ends here
+
+// [[file:../dimer.note::e7e63e56][e7e63e56]]
+#[test]
+fn test_cg_line_search() -> Result<()> {
+    use crate::potentials::QuadraticSaddle;
+
+    #[rustfmt::skip]
+    let hessian = [-1.0,  0.0, 0.0,
+                    0.0, -2.0, 0.0,
+                    0.0,  0.0, 3.0];
+    let pot = QuadraticSaddle::new(&[0.0; 3], &hessian)?;
+    let mut dimer = Dimer::new(&[0.1, 0.2, 0.3], &[1.0, 0.0, 0.0], pot)?;
+    dimer.vars.max_trans_step = 1.0;
+    // real forces [0.1, 0.4, -0.9] inverted along dimer orientation
+    let f_eff = [-0.1, 0.4, -0.9].to_vector();
+
+    // Newton step from the midpoint is exact for positive curvature
+    let d = [0.0, 0.0, 1.0].to_vector();
+    let step = dimer.line_search_along(&d, &f_eff, -1.0)?;
+    approx::assert_relative_eq!(step, -0.3 * &d, epsilon = 1e-8);
+
+    // fall back to the maximum step along forces for negative curvature
+    let d = [0.0, 1.0, 0.0].to_vector();
+    let step = dimer.line_search_along(&d, &f_eff, -1.0)?;
+    approx::assert_relative_eq!(step, 1.0 * &d, epsilon = 1e-8);
+
+    Ok(())
+}
+
+// e7e63e56 ends here
+
+// [[file:../dimer.note::501170d6][501170d6]]
+#[test]
+fn test_translation_displacement() -> Result<()> {
+    use crate::potentials::QuadraticSaddle;
+    use crate::translation::TranslationOptimizer;
+
+    let pot = QuadraticSaddle::from_curvatures(&[0.0; 2], &[-1.0, 2.0])?;
+    let mut dimer = Dimer::new(&[0.1, 0.2], &[1.0, 0.0], pot)?;
+    dimer.vars.max_trans_step = 1.0;
+    let raw_dimer = |f0: [f64; 2]| RawDimer {
+        r0: DVector::zeros(2),
+        r1: DVector::zeros(2),
+        f0: f0.to_vector(),
+        f1: DVector::zeros(2),
+        f2: None,
+    };
+
+    // drag up along the mode with a fixed step in concave region
+    let mut opt = TranslationOptimizer::new(&dimer.vars);
+    let f_eff = [0.1, 0.0].to_vector();
+    let d = dimer.next_translation_displacement(&mut opt, &raw_dimer([-0.1, -0.4]), &f_eff, 1.0, 0.0)?;
+    approx::assert_relative_eq!(d, [0.1, 0.0].to_vector(), epsilon = 1e-12);
+    // no NaN step for zero effective force at an exact minimum
+    let f_eff = DVector::zeros(2);
+    let d = dimer.next_translation_displacement(&mut opt, &raw_dimer([0.0, 0.0]), &f_eff, 1.0, 0.0)?;
+    approx::assert_relative_eq!(d, [0.1, 0.0].to_vector(), epsilon = 1e-12);
+
+    // Newton step along the mode reaches the saddle point along the mode
+    dimer.vars.use_newton_step_along_mode = true;
+    let mut opt = TranslationOptimizer::new(&dimer.vars);
+    let f_eff = [-0.1, -0.4].to_vector();
+    let d = dimer.next_translation_displacement(&mut opt, &raw_dimer([0.1, -0.4]), &f_eff, -1.0, 0.0)?;
+    approx::assert_relative_eq!(d, [-0.1, -0.004].to_vector(), epsilon = 1e-12);
+
+    // trust radius shrinks on a bad prediction and grows on a good one
+    dimer.vars.use_newton_step_along_mode = false;
+    dimer.vars.use_trust_radius = true;
+    dimer.vars.trans_step_size = 1.0;
+    let mut opt = TranslationOptimizer::new(&dimer.vars);
+    let f = [0.0, 1.0];
+    let d = dimer.next_translation_displacement(&mut opt, &raw_dimer(f), &f.to_vector(), -1.0, 0.0)?;
+    approx::assert_relative_eq!(d.norm(), 0.1, epsilon = 1e-12);
+    // predicted -0.1, but energy goes up
+    let d = dimer.next_translation_displacement(&mut opt, &raw_dimer(f), &f.to_vector(), -1.0, 0.05)?;
+    approx::assert_relative_eq!(d.norm(), 0.05, epsilon = 1e-12);
+    // predicted -0.05 as it is
+    let d = dimer.next_translation_displacement(&mut opt, &raw_dimer(f), &f.to_vector(), -1.0, 0.0)?;
+    approx::assert_relative_eq!(d.norm(), 0.1, epsilon = 1e-12);
+
+    Ok(())
+}
+// 501170d6 ends here
+
+// [[file:../dimer.note::2c8f7d05][2c8f7d05]]
+#[test]
+fn test_raw_dimer_central() -> Result<()> {
+    let mut raw_dimer = get_raw_dimer()?;
+    let c_forward = raw_dimer.extrapolate().curvature();
+    assert!(raw_dimer.curvature_error().is_none());
+
+    // central difference with extrapolated endpoint 2 agrees with forward difference
+    raw_dimer.f2 = Some(2.0 * &raw_dimer.f0 - &raw_dimer.f1);
+    let c_central = raw_dimer.extrapolate().curvature();
+    approx::assert_relative_eq!(c_central, c_forward, epsilon = 1e-6);
+    approx::assert_relative_eq!(raw_dimer.curvature_error().unwrap(), 0.0, epsilon = 1e-6);
+
+    Ok(())
+}
+// 2c8f7d05 ends here
+
+// [[file:../dimer.note::71b0e4d8][71b0e4d8]]
+#[test]
+fn test_user_options_validate() {
+    let vars = UserOptions::default();
+    assert!(vars.validate().is_ok());
+
+    let vars = UserOptions {
+        distance: 0.0,
+        ..Default::default()
+    };
+    assert!(vars.validate().is_err());
+
+    let vars = UserOptions {
+        trial_rot_angle: PI / 2.0,
+        ..Default::default()
+    };
+    assert!(vars.validate().is_err());
+
+    let vars = UserOptions {
+        max_num_rot: 0,
+        ..Default::default()
+    };
+    assert!(vars.validate().is_err());
+
+    let vars = UserOptions {
+        min_rot_angle: PI / 3.0,
+        trial_rot_angle: PI / 4.0,
+        ..Default::default()
+    };
+    assert!(vars.validate().is_err());
+}
+// 71b0e4d8 ends here
+
+// [[file:../dimer.note::3db2965d][3db2965d]]
+#[test]
+fn test_user_options_load() -> Result<()> {
+    let dir = std::env::temp_dir();
+    let pid = std::process::id();
+    let env = |vars: &[(&str, &str)]| -> Vec<(String, String)> {
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    };
+
+    // config file format by extension
+    let toml_file = dir.join(format!("dimer-options-{pid}.toml"));
+    gut::fs::write_to_file(&toml_file, "max_num_rot = 7\nfmax = 0.05\ncheckpoint_file = \"a.json\"\n")?;
+    let vars = UserOptions::from_file(&toml_file)?;
+    assert_eq!(vars.max_num_rot, 7);
+    assert_eq!(vars.fmax, 0.05);
+    assert_eq!(vars.checkpoint_file, Some("a.json".into()));
+    let json_file = dir.join(format!("dimer-options-{pid}.json"));
+    gut::fs::write_to_file(&json_file, r#"{"max_num_rot": 8, "use_lbfgs_rot": true}"#)?;
+    let vars = UserOptions::from_file(&json_file)?;
+    assert_eq!(vars.max_num_rot, 8);
+    assert!(vars.use_lbfgs_rot);
+
+    // environment variables > config file > default values
+    let env_vars = env(&[
+        ("DIMER_MAX_NUM_ROT", "11"),
+        ("DIMER_USE_CG_ROT", "false"),
+        ("DIMER_CHECKPOINT_FILE", "123"),
+        ("PATH", "/usr/bin"),
+    ]);
+    let vars = UserOptions::load_with_env(Some(toml_file.as_path()), env_vars)?;
+    assert_eq!(vars.max_num_rot, 11);
+    assert!(!vars.use_cg_rot);
+    assert_eq!(vars.checkpoint_file, Some("123".into()));
+    assert_eq!(vars.fmax, 0.05);
+    assert_eq!(vars.distance, UserOptions::default().distance);
+
+    // invalid values and unknown options
+    let env_vars = env(&[("DIMER_MAX_NUM_ROT", "many")]);
+    let vars = UserOptions::load_with_env(None, env_vars);
+    assert!(matches!(vars, Err(DimerError::InvalidOptions(_))));
+    let env_vars = env(&[("DIMER_MAX_NUM_ROTS", "11")]);
+    let vars = UserOptions::load_with_env(None, env_vars);
+    assert!(matches!(vars, Err(DimerError::InvalidOptions(_))));
+    gut::fs::write_to_file(&json_file, r#"{"max_num_rot": 0}"#)?;
+    assert!(matches!(UserOptions::from_file(&json_file), Err(DimerError::InvalidOptions(_))));
+
+    std::fs::remove_file(&toml_file)?;
+    std::fs::remove_file(&json_file)?;
+
+    Ok(())
+}
+// 3db2965d ends here
+
+// [[file:../dimer.note::d2e61b07][d2e61b07]]
+#[test]
+fn test_rigid_body_modes() {
+    use crate::rigid::{project_out, rigid_body_modes};
+
+    // nonlinear water-like molecule: 6 modes
+    let positions = [0.0, 0.0, 0.1, 0.0, 0.76, -0.47, 0.0, -0.76, -0.47];
+    let modes = rigid_body_modes(&positions, None);
+    assert_eq!(modes.len(), 6);
+    for (i, mi) in modes.iter().enumerate() {
+        for (j, mj) in modes.iter().enumerate() {
+            let expected = if i == j { 1.0 } else { 0.0 };
+            approx::assert_relative_eq!(mi.dot(mj), expected, epsilon = 1e-8);
+        }
+    }
+    // pure rigid translation vanishes after projection
+    let v = [1.0, 2.0, 3.0, 1.0, 2.0, 3.0, 1.0, 2.0, 3.0].to_vector();
+    approx::assert_relative_eq!(project_out(&v, &modes).norm(), 0.0, epsilon = 1e-8);
+
+    // linear molecule: 5 modes
+    let positions = [0.0, 0.0, -1.16, 0.0, 0.0, 0.0, 0.0, 0.0, 1.16];
+    assert_eq!(rigid_body_modes(&positions, None).len(), 5);
+}
+// d2e61b07 ends here
+
+// [[file:../dimer.note::e83b9d12][e83b9d12]]
+#[test]
+fn test_potentials_gradient() {
+    use crate::potentials::{LepsHarmonic, MullerBrown};
+
+    let h = 1e-6;
+    let cases: [(fn(f64, f64) -> (f64, [f64; 2]), [f64; 2]); 2] = [
+        (MullerBrown::energy_gradient, [0.1, 0.3]),
+        (LepsHarmonic::energy_gradient, [1.0, 0.2]),
+    ];
+    for (f, [x, y]) in cases {
+        let (_, g) = f(x, y);
+        let gx = (f(x + h, y).0 - f(x - h, y).0) / (2.0 * h);
+        let gy = (f(x, y + h).0 - f(x, y - h).0) / (2.0 * h);
+        approx::assert_relative_eq!(g[0], gx, epsilon = 1e-5);
+        approx::assert_relative_eq!(g[1], gy, epsilon = 1e-5);
+    }
+
+    // reference saddle points are stationary
+    let ([x, y], e) = MullerBrown::SADDLES[1];
+    let (energy, g) = MullerBrown::energy_gradient(x, y);
+    approx::assert_relative_eq!(energy, e, epsilon = 1e-4);
+    assert!(g[0].abs() < 1e-4 && g[1].abs() < 1e-4);
+    let ([x, y], e) = LepsHarmonic::SADDLE;
+    let (energy, g) = LepsHarmonic::energy_gradient(x, y);
+    approx::assert_relative_eq!(energy, e, epsilon = 1e-6);
+    assert!(g[0].abs() < 1e-6 && g[1].abs() < 1e-6);
+}
+// e83b9d12 ends here
+
+// [[file:../dimer.note::7a4e0c92][7a4e0c92]]
+#[test]
+fn test_displacement_generator() -> Result<()> {
+    // three atoms on a line, the last one far away
+    let positions = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 10.0, 0.0, 0.0];
+    let options = DisplacementOptions {
+        atoms: vec![0],
+        radius: 2.0,
+        seed: 7,
+        ..Default::default()
+    };
+    let (center, orientation) = DisplacementGenerator::new(options.clone()).generate(&positions)?;
+    approx::assert_relative_eq!(orientation.to_vector().norm(), 1.0, epsilon = 1e-8);
+    // the atom out of radius stays fixed
+    assert_eq!(&center[6..], &positions[6..]);
+    assert!(center[..6] != positions[..6]);
+
+    // reproducible with the same seed
+    let (center2, _) = DisplacementGenerator::new(options).generate(&positions)?;
+    assert_eq!(center, center2);
+
+    Ok(())
+}
+// 7a4e0c92 ends here
+
+// [[file:../dimer.note::b0e57c4d][b0e57c4d]]
+#[test]
+fn test_aligned_rmsd() -> Result<()> {
+    use crate::rmsd::aligned_rmsd;
+
+    let a = [0.0, 0.0, 0.1, 0.0, 0.76, -0.47, 0.0, -0.76, -0.47];
+    // rotate by 90 degrees around z axis and translate
+    let b = a
+        .as_3d()
+        .iter()
+        .flat_map(|[x, y, z]| [-y + 1.0, *x, z + 2.0])
+        .collect_vec();
+    approx::assert_relative_eq!(aligned_rmsd(&a, &b, None)?, 0.0, epsilon = 1e-8);
+
+    // swap the two hydrogen atoms
+    let mut c = b.clone();
+    c[3..6].copy_from_slice(&b[6..9]);
+    c[6..9].copy_from_slice(&b[3..6]);
+    assert!(aligned_rmsd(&a, &c, None)? > 0.1);
+    let elements = ["O", "H", "H"].map(String::from);
+    approx::assert_relative_eq!(aligned_rmsd(&a, &c, Some(&elements))?, 0.0, epsilon = 1e-8);
+    assert!(aligned_rmsd(&a, &c, Some(&elements[..2])).is_err());
+
+    Ok(())
+}
+// b0e57c4d ends here
+
+// [[file:../dimer.note::46f1b8e2][46f1b8e2]]
+#[test]
+fn test_vineyard_rate() -> Result<()> {
+    let unit = |i: usize| (0..4).map(|j| if i == j { 1.0 } else { 0.0 }).collect_vec();
+    let minimum = NormalModes {
+        eigenvalues: vec![0.0; 4],
+        modes: (0..4).map(unit).collect(),
+        frequencies: vec![1.0, 100.0, 200.0, 300.0],
+    };
+    let saddle = NormalModes {
+        eigenvalues: vec![0.0; 4],
+        modes: (0..4).map(unit).collect(),
+        frequencies: vec![-500.0, 1.0, 150.0, 250.0],
+    };
+    let options = HtstOptions {
+        temperature: 500.0,
+        ..Default::default()
+    };
+    let r = vineyard_rate(&minimum, &saddle, &unit(0), 0.5, &options)?;
+    assert_eq!(r.n_modes_minimum, 3);
+    assert_eq!(r.n_modes_saddle, 2);
+    approx::assert_relative_eq!(r.unstable_frequency, -500.0);
+    // 100 * 200 * 300 / (150 * 250) cm⁻¹ in Hz
+    approx::assert_relative_eq!(r.prefactor, 160.0 * 2.99792458E10, max_relative = 1e-10);
+    let rate = r.prefactor * (-0.5 / (8.617333262E-5 * 500.0)).exp();
+    approx::assert_relative_eq!(r.rate, rate, max_relative = 1e-10);
+
+    Ok(())
+}
+// 46f1b8e2 ends here
@@ -0,0 +1,70 @@
+// [[file:../dimer.note::5e8b1c2f][5e8b1c2f]]
+use super::*;
+
+use std::io::Write;
+// 5e8b1c2f ends here
+
+// [[file:../dimer.note::c9a4f7d1][c9a4f7d1]]
+/// The kind of iteration in DIMER search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IterationKind {
+    Rotation,
+    Translation,
+}
+
+/// Structured record of one rotation or translation iteration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IterationRecord {
+    /// The kind of iteration
+    pub kind: IterationKind,
+    /// The iteration number counted in its own kind
+    pub iteration: usize,
+    /// The potential energy at dimer center
+    pub energy: f64,
+    /// The curvature estimated in Fourier series
+    pub curvature_est: Option<f64>,
+    /// The curvature from finite differences of forces
+    pub curvature: f64,
+    /// The estimated rotational angle in radians
+    pub phi_est: Option<f64>,
+    /// The optimal rotational angle in radians
+    pub phi_min: Option<f64>,
+    /// The max component of the effective force
+    pub fmax: Option<f64>,
+    /// The total rotational angle in radians within rotation step
+    pub rotation_angle: Option<f64>,
+    /// The length of translation step
+    pub step_length: Option<f64>,
+    /// The total number of force calls so far
+    pub force_calls: usize,
+}
+// c9a4f7d1 ends here
+
+// [[file:../dimer.note::17d3e8a0][17d3e8a0]]
+impl<'a> Dimer<'a> {
+    /// Return the records of all rotation and translation iterations.
+    pub fn records(&self) -> &[IterationRecord] {
+        &self.records
+    }
+
+    /// Clear all iteration records, and truncate the trajectory file if any.
+    pub(crate) fn reset_trajectory(&mut self) -> Result<()> {
+        self.records.clear();
+        if let Some(path) = &self.vars.trajectory_file {
+            std::fs::File::create(path)?;
+        }
+        Ok(())
+    }
+
+    /// Save iteration `record`, and stream it into the trajectory file in JSON
+    /// Lines format if any.
+    pub(crate) fn record(&mut self, record: IterationRecord) -> Result<()> {
+        if let Some(path) = &self.vars.trajectory_file {
+            let mut f = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(f, "{}", serde_json::to_string(&record)?)?;
+        }
+        self.records.push(record);
+        Ok(())
+    }
+}
+// 17d3e8a0 ends here
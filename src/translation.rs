@@ -1,5 +1,9 @@
 // [[file:../dimer.note::b46a5a4d][b46a5a4d]]
 use super::*;
+
+use crate::cg::CG;
+use crate::fire::Fire;
+use crate::lbfgs::Lbfgs;
 // b46a5a4d ends here
 
 // [[file:../dimer.note::5205fe0e][5205fe0e]]
@@ -12,18 +16,273 @@ impl<'a> Dimer<'a> {
     ///
     /// * c_min: optimized curvature value in rotation step
     ///
-    pub(crate) fn next_translation_step(&mut self, raw_dimer: &mut RawDimer, c_min: f64) -> DVector {
+    pub(crate) fn next_translation_step(&mut self, raw_dimer: &RawDimer, c_min: f64) -> DVector {
         // re-use the energy and forces evaluated at rotation step
-        let f0 = &raw_dimer.f0;
-        let t_min = &self.orientation;
+        let f_eff = self.effective_force(&raw_dimer.f0, c_min);
+        // frozen coordinates should never move
+        self.constrain(&f_eff)
+    }
 
-        // update gradient for dimer translation
-        if c_min.is_sign_positive() {
-            info!("drag up directly");
-            -f0.vector_projection(t_min)
+    /// Return the modified force for translation from real forces `f0`, with
+    /// force components inverted along dimer orientation with curvature
+    /// `c_min`, and along higher modes in index-k saddle point search.
+    fn effective_force(&self, f0: &DVector, c_min: f64) -> DVector {
+        let t_min = &self.orientation;
+        if self.higher_modes.is_empty() {
+            if c_min.is_sign_positive() {
+                info!("drag up directly");
+            }
+            compute_effective_force(f0, t_min, c_min)
         } else {
-            f0 - 2.0 * f0.vector_projection(t_min)
+            let modes = std::iter::once(t_min).chain(self.higher_modes.iter().map(|(_, m)| m)).collect_vec();
+            let c_max = self.higher_modes.iter().fold(c_min, |acc, (c, _)| acc.max(*c));
+            if c_max.is_sign_positive() {
+                info!("drag up directly along {} modes", modes.len());
+            }
+            compute_effective_force_multi(f0, &modes, c_max)
         }
     }
 }
+
+/// Return the modified force for DIMER translation, with force component
+/// along `t_min` inverted.
+fn compute_effective_force(f0: &DVector, t_min: &DVector, c_min: f64) -> DVector {
+    if c_min.is_sign_positive() {
+        -f0.vector_projection(t_min)
+    } else {
+        f0 - 2.0 * f0.vector_projection(t_min)
+    }
+}
+
+/// Return the modified force with force components along all orthonormal
+/// `modes` inverted for higher-order saddle point search. If the largest
+/// curvature `c_max` of these modes is positive, only the inverted components
+/// along these modes are kept.
+fn compute_effective_force_multi(f0: &DVector, modes: &[&DVector], c_max: f64) -> DVector {
+    let f_par = modes.iter().fold(DVector::zeros(f0.len()), |acc, m| acc + f0.dot(m) * *m);
+    if c_max.is_sign_positive() {
+        -f_par
+    } else {
+        f0 - 2.0 * f_par
+    }
+}
 // 5205fe0e ends here
+
+// [[file:../dimer.note::6a4d2f83][6a4d2f83]]
+/// Reset translation history if the dimer orientation changed more than this
+/// angle (in radians) between two successive translation steps.
+const MAX_ORIENTATION_CHANGE: f64 = PI / 6.0;
+
+/// Stateful optimizer for taking DIMER translation steps on the effective force
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct TranslationOptimizer {
+    lbfgs: Lbfgs,
+    fire: Fire,
+    cg: CG,
+    /// The curvature and dimer orientation in previous step
+    previous: Option<(f64, DVector)>,
+    /// Current trust radius for translation step
+    trust_radius: f64,
+    /// The energy, predicted energy change and step length of previous step
+    prediction: Option<(f64, f64, f64)>,
+}
+
+impl TranslationOptimizer {
+    pub(crate) fn new(vars: &UserOptions) -> Self {
+        Self {
+            trust_radius: vars.trust_radius,
+            prediction: None,
+            lbfgs: Lbfgs::new(vars.lbfgs_memory, vars.trans_step_size),
+            fire: Fire::new(vars.fire_dt, vars.fire_dt_max, vars.fire_alpha, vars.fire_n_min),
+            cg: CG::new(vars.cg_trans_beta, vars.cg_trans_restart),
+            previous: None,
+        }
+    }
+
+    /// Drop optimization history.
+    fn reset(&mut self) {
+        self.lbfgs.reset();
+        self.fire.reset();
+        self.cg.reset();
+    }
+
+    /// Check if the optimization history is invalid due to a flip of curvature
+    /// sign or a large change of dimer orientation.
+    fn history_invalidated(&self, c_min: f64, orientation: &DVector) -> bool {
+        if let Some((c_prev, n_prev)) = &self.previous {
+            if c_prev.is_sign_positive() != c_min.is_sign_positive() {
+                info!("curvature sign flipped: {c_prev:.4} => {c_min:.4}");
+                return true;
+            }
+            // dimer orientation is only defined up to a sign
+            let phi = orientation.cosine_similarity(n_prev).abs().min(1.0).acos();
+            if phi > MAX_ORIENTATION_CHANGE {
+                info!("dimer orientation changed too much: {:.2}°", phi.to_degrees());
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Update trust radius using the ratio of actual to predicted energy
+    /// change in previous step.
+    fn update_trust_radius(&mut self, energy: f64, vars: &UserOptions) {
+        if let Some((e_prev, de_pred, step)) = self.prediction.take() {
+            if de_pred.abs() < 1E-12 {
+                return;
+            }
+            let de = energy - e_prev;
+            let ratio = de / de_pred;
+            let r = self.trust_radius;
+            if ratio < 0.25 {
+                self.trust_radius = (0.5 * r).max(vars.min_trust_radius);
+            } else if ratio > 0.75 && step > 0.99 * r {
+                self.trust_radius = (2.0 * r).min(vars.max_trans_step);
+            }
+            debug!(
+                "trust radius: {r:.4} => {:.4}; energy change actual/predicted = {de:.4}/{de_pred:.4}",
+                self.trust_radius
+            );
+        }
+    }
+}
+
+/// Predicted energy change for `displacement` in a quadratic model using real
+/// forces `f0` and curvature `c_min` along dimer orientation `t_min`.
+fn predict_energy_change(f0: &DVector, t_min: &DVector, c_min: f64, displacement: &DVector) -> f64 {
+    let x = displacement.dot(t_min);
+    -f0.dot(displacement) + 0.5 * c_min * x * x
+}
+
+/// Scale down `displacement` if its length exceeds `max_step`.
+pub(crate) fn limit_step_length(displacement: DVector, max_step: f64) -> DVector {
+    let norm = displacement.norm();
+    if norm > max_step {
+        displacement * (max_step / norm)
+    } else {
+        displacement
+    }
+}
+
+impl<'a> Dimer<'a> {
+    /// Return displacement of dimer center in translation step using
+    /// `effective_force` from `next_translation_step`.
+    ///
+    /// # Parameters
+    ///
+    /// * optimizer: translation optimizer holding history data
+    /// * raw_dimer: the dimer with real forces evaluated at center
+    /// * effective_force: the modified force for DIMER translation
+    /// * c_min: optimized curvature value in rotation step, or the largest
+    ///   curvature of inverted modes in index-k saddle point search
+    /// * energy: the potential energy at dimer center
+    ///
+    pub(crate) fn next_translation_displacement(
+        &mut self,
+        optimizer: &mut TranslationOptimizer,
+        raw_dimer: &RawDimer,
+        effective_force: &DVector,
+        c_min: f64,
+        energy: f64,
+    ) -> Result<DVector> {
+        if optimizer.history_invalidated(c_min, &self.orientation) {
+            info!("reset translation optimizer");
+            optimizer.reset();
+        }
+        optimizer.previous = Some((c_min, self.orientation.clone()));
+        if self.vars.use_trust_radius {
+            optimizer.update_trust_radius(energy, &self.vars);
+        }
+
+        let displacement = if c_min.is_sign_positive() {
+            // in concave region: drag up along dimer orientation with a fixed step
+            if effective_force.norm() > 0.0 {
+                self.vars.uphill_step_size * effective_force.normalize()
+            } else {
+                // no force along the mode, e.g. at an exact minimum: either
+                // direction is uphill
+                warn!("zero effective force in concave region; drag up along dimer orientation");
+                self.vars.uphill_step_size * &self.orientation
+            }
+        } else {
+            let mut displacement = match self.vars.translation_method {
+                TranslationMethod::SteepestDescent => self.vars.trans_step_size * effective_force,
+                TranslationMethod::LBFGS => {
+                    let mut displacement = optimizer.lbfgs.propagate(&self.center, effective_force);
+                    // fall back to steepest descent if L-BFGS step goes uphill
+                    if displacement.dot(effective_force) <= 0.0 {
+                        warn!("L-BFGS step is uphill; reset history");
+                        optimizer.lbfgs.reset();
+                        displacement = optimizer.lbfgs.propagate(&self.center, effective_force);
+                    }
+                    displacement
+                }
+                TranslationMethod::FIRE => optimizer.fire.propagate(effective_force),
+                TranslationMethod::CG => {
+                    let direction = optimizer.cg.propagate(effective_force)?;
+                    self.line_search_along(&direction, effective_force, c_min)?
+                }
+            };
+            if self.vars.use_newton_step_along_mode {
+                // the effective force has been inverted along dimer orientation
+                let t_min = &self.orientation;
+                let x = effective_force.dot(t_min) / c_min.abs();
+                displacement = displacement.vector_rejection(t_min) + x * t_min;
+            }
+            displacement
+        };
+
+        let max_step = if self.vars.use_trust_radius {
+            optimizer.trust_radius.min(self.vars.max_trans_step)
+        } else {
+            self.vars.max_trans_step
+        };
+        let displacement = limit_step_length(displacement, max_step);
+        // never move dimer center into invalid coordinates
+        if displacement.iter().any(|x| !x.is_finite()) {
+            return Err(DimerError::Numerical(format!("invalid translation step with curvature {c_min}")));
+        }
+        if self.vars.use_trust_radius {
+            let de_pred = predict_energy_change(&raw_dimer.f0, &self.orientation, c_min, &displacement);
+            optimizer.prediction = Some((energy, de_pred, displacement.norm()));
+        }
+
+        Ok(displacement)
+    }
+
+    /// Take a Newton step along CG `direction` using the curvature estimated
+    /// by finite difference of effective forces in a trial step.
+    /// (Henkelman1999JCP)
+    pub(crate) fn line_search_along(
+        &mut self,
+        direction: &DVector,
+        effective_force: &DVector,
+        c_min: f64,
+    ) -> Result<DVector> {
+        let d = direction.normalize();
+        let dr = self.vars.cg_trans_trial_step;
+        let max_step = self.vars.max_trans_step;
+
+        // trial step using the same dimer orientation
+        let r_trial = &self.center + dr * &d;
+        let f0_trial = self.compute_force_at(&r_trial, ForcePhase::Translation)?;
+        let f_trial = self.effective_force(&f0_trial, c_min);
+
+        // the projected effective forces along the line
+        let f_line = effective_force.dot(&d);
+        let f_line_trial = f_trial.dot(&d);
+        let curvature = (f_line - f_line_trial) / dr;
+        let step = if curvature > 0.0 {
+            // Newton step from the midpoint of trial step
+            0.5 * dr + 0.5 * (f_line + f_line_trial) / curvature
+        } else {
+            debug!("negative curvature along CG direction: {curvature}");
+            max_step.copysign(f_line)
+        };
+        debug!("CG line search: curvature = {curvature:.4}, step = {step:.4}");
+
+        Ok(step.clamp(-max_step, max_step) * d)
+    }
+}
+// 6a4d2f83 ends here
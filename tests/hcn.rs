@@ -28,7 +28,7 @@ fn test_dimer_opt() -> Result<()> {
         Ok(energy)
     };
 
-    let mut dimer = Dimer::new(center.as_flat(), orientation.as_flat(), pot);
+    let mut dimer = Dimer::new(center.as_flat(), orientation.as_flat(), pot)?;
     dimer.vars.min_rot_angle = 1f64.to_radians();
     dimer.vars.max_num_rot = 10;
     dimer.vars.use_extrapolated_force = false;
@@ -0,0 +1,518 @@
+// [[file:../dimer.note::5c7e2a90][5c7e2a90]]
+use dimer::potentials::*;
+use dimer::{Dimer, TranslationMethod};
+
+use approx::*;
+use gut::prelude::*;
+use vecfx::*;
+
+#[test]
+fn test_dimer_muller_brown() -> Result<()> {
+    let (saddle, energy) = MullerBrown::SADDLES[0];
+    let center = [saddle[0] + 0.02, saddle[1] - 0.02];
+    let mut dimer = Dimer::new(&center, &[1.0, 0.0], MullerBrown)?;
+    dimer.vars.fmax = 1E-2;
+    dimer.vars.min_rot_angle = 1f64.to_radians();
+    dimer.vars.max_num_rot = 10;
+    dimer.vars.trans_step_size = 1E-3;
+    dimer.vars.max_num_trans = 200;
+
+    let o = dimer.search()?.ensure_converged()?;
+    assert_relative_eq!(o.saddle.position.to_vector(), saddle.to_vector(), epsilon = 1e-4);
+    assert_relative_eq!(o.saddle.total_energy, energy, epsilon = 1e-4);
+    assert!(o.saddle.curvature < 0.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_dimer_leps_harmonic() -> Result<()> {
+    let (saddle, energy) = LepsHarmonic::SADDLE;
+    let center = [saddle[0] + 0.05, saddle[1] - 0.05];
+    let mut dimer = Dimer::new(&center, &[1.0, 1.0], LepsHarmonic)?;
+    dimer.vars.fmax = 1E-4;
+    dimer.vars.min_rot_angle = 1f64.to_radians();
+    dimer.vars.max_num_rot = 10;
+    dimer.vars.trans_step_size = 0.15;
+    dimer.vars.max_num_trans = 300;
+
+    let o = dimer.search()?.ensure_converged()?;
+    assert_relative_eq!(o.saddle.position.to_vector(), saddle.to_vector(), epsilon = 1e-3);
+    assert_relative_eq!(o.saddle.total_energy, energy, epsilon = 1e-6);
+
+    Ok(())
+}
+
+#[test]
+fn test_dimer_lanczos_muller_brown() -> Result<()> {
+    let (saddle, energy) = MullerBrown::SADDLES[1];
+    let center = [saddle[0] - 0.02, saddle[1] + 0.02];
+    let mut dimer = Dimer::new(&center, &[0.0, 1.0], MullerBrown)?;
+    dimer.vars.use_lanczos = true;
+    dimer.vars.fmax = 1E-2;
+    dimer.vars.min_rot_angle = 1f64.to_radians();
+    dimer.vars.trans_step_size = 1E-3;
+    dimer.vars.max_num_trans = 200;
+
+    let o = dimer.search()?.ensure_converged()?;
+    assert_relative_eq!(o.saddle.position.to_vector(), saddle.to_vector(), epsilon = 1e-4);
+    assert_relative_eq!(o.saddle.total_energy, energy, epsilon = 1e-4);
+    assert!(o.saddle.curvature < 0.0);
+
+    Ok(())
+}
+
+/// Return a dimer close to the saddle point of a quadratic potential, together
+/// with the potential for reference.
+fn quadratic_saddle_dimer() -> Result<(Dimer<'static>, QuadraticSaddle)> {
+    #[rustfmt::skip]
+    let hessian = [-1.0, 0.2, 0.0,
+                    0.2, 2.0, 0.3,
+                    0.0, 0.3, 3.0];
+    let pot = QuadraticSaddle::new(&[0.5, -0.2, 1.0], &hessian)?;
+    let mut dimer = Dimer::new(&[0.55, -0.15, 0.95], &[1.0, 1.0, 1.0], pot.clone())?;
+    dimer.vars.fmax = 1E-5;
+    dimer.vars.min_rot_angle = 1f64.to_radians();
+    dimer.vars.max_num_rot = 10;
+    Ok((dimer, pot))
+}
+
+#[test]
+fn test_dimer_quadratic_saddle() -> Result<()> {
+    let (mut dimer, pot) = quadratic_saddle_dimer()?;
+    let (c_min, mode) = pot.lowest_mode();
+    dimer.vars.translation_method = TranslationMethod::LBFGS;
+    dimer.vars.trans_step_size = 0.2;
+
+    let o = dimer.search()?.ensure_converged()?;
+    assert_relative_eq!(o.saddle.position.to_vector(), pot.saddle().to_vector(), epsilon = 1e-4);
+    assert_relative_eq!(o.saddle.curvature, c_min, epsilon = 1e-3);
+    let overlap = o.saddle.curvature_mode.to_vector().dot(&mode.to_vector());
+    assert_relative_eq!(overlap.abs(), 1.0, epsilon = 1e-4);
+
+    // check the saddle point using finite difference Hessian
+    let v = dimer.verify_saddle(&o.saddle, None)?;
+    assert!(v.is_first_order);
+    assert_relative_eq!(v.mode_overlap, 1.0, epsilon = 1e-4);
+    assert_relative_eq!(v.normal_modes.eigenvalues[0], c_min, epsilon = 1e-6);
+    assert!(v.normal_modes.frequencies[0] < 0.0);
+
+    Ok(())
+}
+// 5c7e2a90 ends here
+
+// [[file:../dimer.note::6c75cd61][6c75cd61]]
+#[test]
+fn test_dimer_fire_translation() -> Result<()> {
+    let (mut dimer, pot) = quadratic_saddle_dimer()?;
+    dimer.vars.translation_method = TranslationMethod::FIRE;
+    dimer.vars.max_num_trans = 500;
+
+    let o = dimer.search()?.ensure_converged()?;
+    assert_relative_eq!(o.saddle.position.to_vector(), pot.saddle().to_vector(), epsilon = 1e-4);
+    assert_relative_eq!(o.saddle.curvature, pot.lowest_mode().0, epsilon = 1e-3);
+
+    Ok(())
+}
+// 6c75cd61 ends here
+
+// [[file:../dimer.note::37d6ef2b][37d6ef2b]]
+#[test]
+fn test_dimer_cg_translation() -> Result<()> {
+    let (mut dimer, pot) = quadratic_saddle_dimer()?;
+    dimer.vars.translation_method = TranslationMethod::CG;
+    let o = dimer.search()?.ensure_converged()?;
+    assert_relative_eq!(o.saddle.position.to_vector(), pot.saddle().to_vector(), epsilon = 1e-4);
+
+    // conjugate directions beat steepest descent on the same surface
+    let (mut dimer, _) = quadratic_saddle_dimer()?;
+    dimer.vars.translation_method = TranslationMethod::SteepestDescent;
+    dimer.vars.trans_step_size = 0.2;
+    let o_sd = dimer.search()?.ensure_converged()?;
+    assert!(o.n_iterations < o_sd.n_iterations);
+
+    Ok(())
+}
+// 37d6ef2b ends here
+
+// [[file:../dimer.note::5fbdc411][5fbdc411]]
+#[test]
+fn test_dimer_lbfgs_rotation() -> Result<()> {
+    #[rustfmt::skip]
+    let hessian = [-0.5, 0.3, 0.1, 0.0,
+                    0.3, 1.0, 0.2, 0.1,
+                    0.1, 0.2, 2.0, 0.3,
+                    0.0, 0.1, 0.3, 3.0];
+    let pot = QuadraticSaddle::new(&[0.0; 4], &hessian)?;
+    let (c_min, mode) = pot.lowest_mode();
+
+    let center = [0.1, -0.1, 0.05, 0.2];
+    let mut dimer = Dimer::new(&center, &[0.2, 1.0, 1.0, 1.0], pot)?;
+    dimer.vars.use_lbfgs_rot = true;
+    dimer.vars.min_rot_angle = 0.1f64.to_radians();
+    dimer.vars.max_num_rot = 50;
+
+    let o = dimer.evaluate()?;
+    assert_relative_eq!(o.curvature, c_min, epsilon = 1e-3);
+    let overlap = o.curvature_mode.to_vector().normalize().dot(&mode.to_vector());
+    assert_relative_eq!(overlap.abs(), 1.0, epsilon = 1e-4);
+
+    Ok(())
+}
+// 5fbdc411 ends here
+
+// [[file:../dimer.note::588ca02c][588ca02c]]
+#[test]
+fn test_dimer_resume_from_checkpoint() -> Result<()> {
+    let setup = || -> Result<_> {
+        let (mut dimer, pot) = quadratic_saddle_dimer()?;
+        dimer.vars.translation_method = TranslationMethod::LBFGS;
+        dimer.vars.trans_step_size = 0.2;
+        Ok((dimer, pot))
+    };
+
+    // uninterrupted search
+    let o = setup()?.0.search()?.ensure_converged()?;
+    assert!(o.n_iterations > 3);
+    assert!(o.n_rotations > 0);
+
+    // interrupted after 3 iterations, then resumed in a new dimer
+    let path = std::env::temp_dir().join(format!("dimer-checkpoint-{}.json", std::process::id()));
+    let (mut dimer, pot) = setup()?;
+    dimer.vars.checkpoint_file = Some(path.clone());
+    dimer.vars.max_num_trans = 3;
+    assert!(!dimer.search()?.converged());
+    let mut dimer = Dimer::resume_from(&path, pot)?;
+    dimer.vars.max_num_trans = 100;
+    let o_resumed = dimer.search()?.ensure_converged()?;
+    std::fs::remove_file(&path)?;
+
+    assert_eq!(o_resumed.n_iterations, o.n_iterations);
+    assert_eq!(o_resumed.n_rotations, o.n_rotations);
+    assert_eq!(o_resumed.force_calls.total(), o.force_calls.total());
+    let p = o.saddle.position.to_vector();
+    assert_relative_eq!(o_resumed.saddle.position.to_vector(), p, epsilon = 1e-10);
+
+    Ok(())
+}
+// 588ca02c ends here
+
+// [[file:../dimer.note::0d8fd6ae][0d8fd6ae]]
+#[test]
+fn test_dimer_force_call_budget() -> Result<()> {
+    use dimer::SearchStatus;
+
+    let budget = 20;
+    let (mut dimer, _) = quadratic_saddle_dimer()?;
+    dimer.vars.max_force_calls = Some(budget);
+
+    let o = dimer.search()?;
+    assert_eq!(o.status, SearchStatus::BudgetExhausted);
+    // overrun bound with forward differences
+    assert!(o.force_calls.total() >= budget);
+    assert!(o.force_calls.total() <= budget + 3);
+
+    Ok(())
+}
+// 0d8fd6ae ends here
+
+// [[file:../dimer.note::c1bb9d36][c1bb9d36]]
+#[test]
+fn test_dimer_frozen_dofs() -> Result<()> {
+    let curvatures = [-1.0, 2.0, 3.0, 1.5];
+    let saddle = [0.3, -0.2, 0.1, 0.0];
+    let center = [0.35, 0.4, 0.05, -0.3];
+    let frozen = [false, true, false, true];
+    let setup = || -> Result<Dimer<'static>> {
+        let pot = QuadraticSaddle::from_curvatures(&saddle, &curvatures)?;
+        let mut dimer = Dimer::new(&center, &[1.0, 1.0, 0.2, 1.0], pot)?;
+        dimer.set_frozen_dofs(&frozen)?;
+        dimer.vars.fmax = 1E-5;
+        dimer.vars.min_rot_angle = 0.5f64.to_radians();
+        dimer.vars.max_num_rot = 20;
+        dimer.vars.translation_method = TranslationMethod::LBFGS;
+        dimer.vars.trans_step_size = 0.2;
+        Ok(dimer)
+    };
+    let check = |o: &dimer::SearchOutput| {
+        for i in [1, 3] {
+            assert_eq!(o.saddle.position[i], center[i]);
+            assert_eq!(o.saddle.curvature_mode[i], 0.0);
+        }
+        for i in [0, 2] {
+            assert_relative_eq!(o.saddle.position[i], saddle[i], epsilon = 1e-4);
+        }
+    };
+
+    let o = setup()?.search()?.ensure_converged()?;
+    check(&o);
+
+    // frozen coordinates stay fixed after restart from checkpoint
+    let path = std::env::temp_dir().join(format!("dimer-frozen-{}.json", std::process::id()));
+    let mut dimer = setup()?;
+    dimer.vars.checkpoint_file = Some(path.clone());
+    dimer.vars.max_num_trans = 2;
+    dimer.search()?;
+    let pot = QuadraticSaddle::from_curvatures(&saddle, &curvatures)?;
+    let mut dimer = Dimer::resume_from(&path, pot)?;
+    dimer.vars.max_num_trans = 100;
+    let o = dimer.search()?.ensure_converged()?;
+    std::fs::remove_file(&path)?;
+    check(&o);
+
+    Ok(())
+}
+// c1bb9d36 ends here
+
+// [[file:../dimer.note::a6c0d3f8][a6c0d3f8]]
+#[test]
+fn test_dimer_lowest_modes() -> Result<()> {
+    let curvatures = [-1.0, 0.5, 2.0, 3.0];
+    let pot = QuadraticSaddle::from_curvatures(&[0.0; 4], &curvatures)?;
+    let center = [0.1, -0.1, 0.05, 0.2];
+    let mut dimer = Dimer::new(&center, &[1.0, 1.0, 1.0, 1.0], pot)?;
+    dimer.vars.min_rot_angle = 0.5f64.to_radians();
+    dimer.vars.max_num_rot = 20;
+
+    let modes = dimer.lowest_modes(3)?;
+    assert_eq!(modes.len(), 3);
+    // forces at dimer center are evaluated only once
+    assert_eq!(dimer.force_calls().reinitialization, 4);
+    for (i, m) in modes.iter().enumerate() {
+        assert_relative_eq!(m.curvature, curvatures[i], epsilon = 1e-2);
+        assert_relative_eq!(m.mode[i].abs(), 1.0, epsilon = 1e-3);
+    }
+
+    Ok(())
+}
+// a6c0d3f8 ends here
+
+// [[file:../dimer.note::3b8f2e64][3b8f2e64]]
+#[test]
+fn test_dimer_index2_saddle() -> Result<()> {
+    let curvatures = [-1.0, -0.5, 2.0];
+    let saddle = [0.3, -0.2, 0.1];
+    let pot = QuadraticSaddle::from_curvatures(&saddle, &curvatures)?;
+    let center = [0.4, -0.1, 0.15];
+    let mut dimer = Dimer::new(&center, &[1.0, 0.2, 0.1], pot)?;
+    dimer.vars.saddle_index = 2;
+    dimer.vars.fmax = 1E-5;
+    dimer.vars.min_rot_angle = 0.5f64.to_radians();
+    dimer.vars.max_num_rot = 20;
+    dimer.vars.trans_step_size = 0.3;
+    dimer.vars.max_num_trans = 200;
+
+    let o = dimer.search()?.ensure_converged()?;
+    assert_relative_eq!(o.saddle.position.to_vector(), saddle.to_vector(), epsilon = 1e-4);
+    assert_eq!(o.saddle.modes.len(), 2);
+    let mut found = o.saddle.modes.iter().map(|m| m.curvature).collect_vec();
+    found.sort_by(|a, b| a.total_cmp(b));
+    assert_relative_eq!(found[0], curvatures[0], epsilon = 1e-2);
+    assert_relative_eq!(found[1], curvatures[1], epsilon = 1e-2);
+
+    // one force call at dimer center and one on endpoint of each mode
+    assert_eq!(o.force_calls.reinitialization, 3 * o.n_iterations);
+
+    let v = dimer.verify_saddle(&o.saddle, None)?;
+    assert_eq!(v.n_negative, 2);
+
+    Ok(())
+}
+// 3b8f2e64 ends here
+
+// [[file:../dimer.note::c2d74a15][c2d74a15]]
+#[test]
+fn test_batch_search() -> Result<()> {
+    use dimer::{batch_search, BatchOptions, BatchStatus, DisplacementOptions, UserOptions};
+
+    let saddle = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+    let curvatures = [-1.0, 1.0, 1.5, 2.0, 2.5, 3.0];
+    let make_pot = || QuadraticSaddle::from_curvatures(&saddle, &curvatures).unwrap();
+    let traj = std::env::temp_dir().join(format!("dimer-batch-{}.jsonl", std::process::id()));
+    let vars = UserOptions {
+        trajectory_file: Some(traj.clone()),
+        fmax: 1E-4,
+        min_rot_angle: 1f64.to_radians(),
+        max_num_rot: 10,
+        trans_step_size: 0.3,
+        max_num_trans: 200,
+        ..Default::default()
+    };
+    let options = BatchOptions {
+        n_searches: 3,
+        displacement: DisplacementOptions {
+            width: 0.05,
+            seed: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let o = batch_search(&saddle, None, &vars, &options, make_pot)?;
+    assert_eq!(o.searches.len(), 3);
+    assert_eq!(o.saddles.len(), 1);
+    assert_eq!(o.saddles[0].count, 3);
+    assert_eq!(o.searches[0].status, BatchStatus::Converged);
+    assert!(o.searches[1..].iter().all(|s| s.status == BatchStatus::Duplicate));
+
+    // each search writes into its own trajectory file
+    assert!(!traj.exists());
+    for i in 0..3 {
+        let path = traj.with_extension(format!("{i}.jsonl"));
+        assert!(path.exists());
+        std::fs::remove_file(&path)?;
+    }
+
+    Ok(())
+}
+// c2d74a15 ends here
+
+// [[file:../dimer.note::f09a6e31][f09a6e31]]
+#[test]
+fn test_dimer_connect_muller_brown() -> Result<()> {
+    use dimer::ConnectOptions;
+
+    let (saddle, _) = MullerBrown::SADDLES[0];
+    let center = [saddle[0] + 0.02, saddle[1] - 0.02];
+    let mut dimer = Dimer::new(&center, &[1.0, 0.0], MullerBrown)?;
+    dimer.vars.fmax = 1E-2;
+    dimer.vars.min_rot_angle = 1f64.to_radians();
+    dimer.vars.max_num_rot = 10;
+    dimer.vars.trans_step_size = 1E-3;
+    dimer.vars.max_num_trans = 200;
+    dimer.vars.max_trans_step = 0.05;
+    dimer.vars.fire_dt = 0.002;
+    dimer.vars.fire_dt_max = 0.01;
+    let o = dimer.search()?.ensure_converged()?;
+
+    let options = ConnectOptions {
+        displacement: 0.05,
+        fmax: 1E-2,
+        max_steps: 5000,
+        ..Default::default()
+    };
+    let c = dimer.connect(&o.saddle, &options)?;
+    assert!(c.reactant.converged && c.product.converged);
+    assert!(!c.same_minimum);
+    // the first saddle point connects the first and the third minima
+    let mut energies = [c.reactant.energy, c.product.energy];
+    energies.sort_by(|a, b| a.total_cmp(b));
+    assert_relative_eq!(energies[0], MullerBrown::MINIMA[0].1, epsilon = 1e-3);
+    assert_relative_eq!(energies[1], MullerBrown::MINIMA[2].1, epsilon = 1e-3);
+    assert_relative_eq!(c.forward_barrier, o.saddle.total_energy - c.reactant.energy, epsilon = 1e-8);
+
+    // no direction to leave the saddle point without a curvature mode
+    let mut saddle = o.saddle.clone();
+    saddle.curvature_mode = vec![0.0; 2];
+    assert!(dimer.connect(&saddle, &options).is_err());
+
+    Ok(())
+}
+// f09a6e31 ends here
+
+// [[file:../dimer.note::375c48d1][375c48d1]]
+/// Double well along the first coordinate coupled with harmonic oscillators:
+/// E = a (x₀² - 1)² + ½ (1 + b x₀²) Σ kᵢ xᵢ², with minima at x₀ = ±1 and a
+/// first-order saddle point at the origin.
+#[derive(Debug, Clone)]
+struct DoubleWellHarmonic {
+    a: f64,
+    b: f64,
+    k: Vec<f64>,
+}
+
+impl dimer::EvaluateEnergyForce for DoubleWellHarmonic {
+    fn evaluate(&mut self, position: &[f64], force: &mut [f64]) -> Result<f64> {
+        let x0 = position[0];
+        let s = 1.0 + self.b * x0 * x0;
+        let kx2: f64 = self.k.iter().zip(&position[1..]).map(|(k, x)| k * x * x).sum();
+        force[0] = -(4.0 * self.a * x0 * (x0 * x0 - 1.0) + self.b * x0 * kx2);
+        for (i, k) in self.k.iter().enumerate() {
+            force[i + 1] = -s * k * position[i + 1];
+        }
+        Ok(self.a * (x0 * x0 - 1.0).powi(2) + 0.5 * s * kx2)
+    }
+}
+
+#[test]
+fn test_dimer_htst_rate() -> Result<()> {
+    use dimer::HtstOptions;
+
+    let (a, b) = (0.5, 0.5);
+    let k = vec![1.0, 2.0, 3.0, 1.5, 2.5];
+    let pot = DoubleWellHarmonic { a, b, k: k.clone() };
+    let center = [0.05, 0.05, -0.05, 0.05, 0.03, -0.02];
+    let mut dimer = Dimer::new(&center, &[1.0, 0.3, 0.3, 0.3, 0.3, 0.3], pot)?;
+    dimer.vars.fmax = 1E-6;
+    dimer.vars.min_rot_angle = 1f64.to_radians();
+    dimer.vars.max_num_rot = 10;
+    dimer.vars.translation_method = TranslationMethod::LBFGS;
+    dimer.vars.trans_step_size = 0.2;
+    let o = dimer.search()?.ensure_converged()?;
+    assert_relative_eq!(o.saddle.position.to_vector(), [0.0; 6].to_vector(), epsilon = 1e-5);
+
+    // two atoms, each with three coordinates
+    let masses = [1.0, 12.0];
+    let minimum = [1.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+    let options = HtstOptions::default();
+    let r = dimer.htst_rate(&minimum, &o.saddle, a, &masses, &options)?;
+    assert_eq!(r.n_modes_minimum, 6);
+    assert_eq!(r.n_modes_saddle, 5);
+
+    // analytic harmonic frequencies in cm⁻¹ from diagonal Hessians
+    let nu = |c: f64, m: f64| c.signum() * (c.abs() / m).sqrt() * 521.47090;
+    let m = |i: usize| masses[(i + 1) / 3];
+    let nu_min = nu(8.0 * a, masses[0]) * (0..5).map(|i| nu((1.0 + b) * k[i], m(i))).product::<f64>();
+    let nu_sad = (0..5).map(|i| nu(k[i], m(i))).product::<f64>();
+    let prefactor = nu_min / nu_sad * 2.99792458E10;
+    assert_relative_eq!(r.prefactor, prefactor, max_relative = 1e-5);
+    assert_relative_eq!(r.unstable_frequency, nu(-4.0 * a, masses[0]), max_relative = 1e-5);
+    assert_relative_eq!(r.rate, prefactor * (-a / (8.617333262E-5 * 300.0)).exp(), max_relative = 1e-5);
+
+    Ok(())
+}
+// 375c48d1 ends here
+
+// [[file:../dimer.note::c4e17b52][c4e17b52]]
+#[test]
+fn test_dimer_mass_weighted() -> Result<()> {
+    #[rustfmt::skip]
+    let hessian = [-1.0, 0.0, 0.0, 0.8, 0.0, 0.0,
+                    0.0, 1.5, 0.0, 0.0, 0.0, 0.0,
+                    0.0, 0.0, 2.0, 0.0, 0.0, 0.0,
+                    0.8, 0.0, 0.0, 2.5, 0.0, 0.0,
+                    0.0, 0.0, 0.0, 0.0, 3.0, 0.0,
+                    0.0, 0.0, 0.0, 0.0, 0.0, 3.5];
+    let saddle = [0.2, 0.1, -0.3, 1.2, -0.1, 0.4];
+    let masses = [1.0, 12.0];
+    let pot = QuadraticSaddle::new(&saddle, &hessian)?;
+    let (_, cartesian_mode) = pot.lowest_mode();
+
+    let center = [0.25, 0.12, -0.28, 1.22, -0.12, 0.38];
+    let mut dimer = Dimer::new(&center, &[1.0, 0.0, 0.0, 0.0, 0.0, 0.0], pot)?;
+    dimer.set_masses(&masses)?;
+    dimer.vars.fmax = 1E-5;
+    dimer.vars.min_rot_angle = 0.5f64.to_radians();
+    dimer.vars.max_num_rot = 20;
+    dimer.vars.translation_method = TranslationMethod::LBFGS;
+    dimer.vars.trans_step_size = 0.2;
+    dimer.vars.max_num_trans = 200;
+
+    let o = dimer.search()?.ensure_converged()?;
+    // reported results are in Cartesian coordinates
+    assert_relative_eq!(o.saddle.position.to_vector(), saddle.to_vector(), epsilon = 1e-4);
+    let mode = o.saddle.curvature_mode.to_vector();
+    assert_relative_eq!(mode.norm(), 1.0, epsilon = 1e-8);
+    assert!(o.saddle.effective_force.to_vector().amax() < 1E-3);
+
+    // the curvature mode is the vibrational normal mode, not the lowest
+    // Cartesian mode
+    let v = dimer.verify_saddle(&o.saddle, Some(&masses))?;
+    assert!(v.is_first_order);
+    assert_relative_eq!(v.mode_overlap, 1.0, epsilon = 1e-3);
+    assert_relative_eq!(o.saddle.curvature, v.normal_modes.eigenvalues[0], epsilon = 1e-3);
+    assert!(mode.dot(&cartesian_mode.to_vector()).abs() < 0.995);
+
+    Ok(())
+}
+// c4e17b52 ends here